@@ -0,0 +1,50 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+
+fn numbers() -> String {
+    let items: Vec<String> = (0..10_000)
+        .map(|i| match i % 3 {
+            0 => i.to_string(),
+            1 => format!("-{i}.{i}"),
+            _ => format!("{i}e-3"),
+        })
+        .collect();
+    format!("[{}]", items.join(", "))
+}
+
+fn strings() -> String {
+    let items: Vec<String> = (0..10_000)
+        .map(|i| match i % 4 {
+            0 => format!("\"escaped \\\"{i}\\\"\\n\""),
+            _ => format!("\"plain string number {i}\""),
+        })
+        .collect();
+    format!("[{}]", items.join(", "))
+}
+
+fn objects() -> String {
+    let items: Vec<String> = (0..2_000)
+        .map(|i| format!("{{\"id\": {i}, \"name\": \"item {i}\", \"tags\": [\"a\", \"b\"], \"ok\": true, \"parent\": null}}"))
+        .collect();
+    format!("[{}]", items.join(", "))
+}
+
+fn bench_parse(c: &mut Criterion) {
+    for (name, input) in [
+        ("numbers", numbers()),
+        ("strings", strings()),
+        ("objects", objects()),
+    ] {
+        let mut group = c.benchmark_group(name);
+        group.bench_function("parse", |b| b.iter(|| jsnom::parse(black_box(&input))));
+        group.bench_function("parse_deep", |b| {
+            b.iter(|| jsnom::parse_deep(black_box(&input)))
+        });
+        group.bench_function("parse_borrowed", |b| {
+            b.iter(|| jsnom::parse_borrowed(black_box(&input)))
+        });
+        group.finish();
+    }
+}
+
+criterion_group!(benches, bench_parse);
+criterion_main!(benches);
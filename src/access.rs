@@ -0,0 +1,581 @@
+use alloc::{string::String, vec::Vec};
+use core::ops::Index;
+
+use crate::{JsonValue, Map, Number};
+
+/// A type that can be used to look up a value inside a [`JsonValue`] with [`JsonValue::get`].
+///
+/// Implemented for `&str`, which looks up a key in an object, and `usize`, which looks up an
+/// element of an array. This trait is sealed and cannot be implemented outside of `jsnom`.
+pub trait JsonIndex: private::Sealed {
+    #[doc(hidden)]
+    fn index_into<'v>(&self, value: &'v JsonValue) -> Option<&'v JsonValue>;
+    #[doc(hidden)]
+    fn index_into_mut<'v>(&self, value: &'v mut JsonValue) -> Option<&'v mut JsonValue>;
+}
+
+mod private {
+    pub trait Sealed {}
+    impl Sealed for &str {}
+    impl Sealed for usize {}
+}
+
+impl JsonIndex for &str {
+    fn index_into<'v>(&self, value: &'v JsonValue) -> Option<&'v JsonValue> {
+        match value {
+            JsonValue::Object(map) => map.get(self),
+            _ => None,
+        }
+    }
+
+    fn index_into_mut<'v>(&self, value: &'v mut JsonValue) -> Option<&'v mut JsonValue> {
+        match value {
+            JsonValue::Object(map) => map.get_mut(self),
+            _ => None,
+        }
+    }
+}
+
+impl JsonIndex for usize {
+    fn index_into<'v>(&self, value: &'v JsonValue) -> Option<&'v JsonValue> {
+        match value {
+            JsonValue::Array(items) => items.get(*self),
+            _ => None,
+        }
+    }
+
+    fn index_into_mut<'v>(&self, value: &'v mut JsonValue) -> Option<&'v mut JsonValue> {
+        match value {
+            JsonValue::Array(items) => items.get_mut(*self),
+            _ => None,
+        }
+    }
+}
+
+impl JsonValue {
+    /// Look up a key in an object or an element in an array, returning `None` if it is missing
+    /// or the value is of the wrong type. If an object key appears more than once, the last
+    /// entry is used.
+    ///
+    /// ```
+    /// use jsnom::JsonValue;
+    ///
+    /// let value = JsonValue::from_str("{\"user\": {\"name\": \"Piturnah\"}, \"crates\": []}").unwrap();
+    /// assert_eq!(
+    ///     value.get("user").and_then(|u| u.get("name")),
+    ///     Some(&JsonValue::String("Piturnah".to_string()))
+    /// );
+    /// assert_eq!(value.get("crates").and_then(|c| c.get(0)), None);
+    /// ```
+    pub fn get<I: JsonIndex>(&self, index: I) -> Option<&JsonValue> {
+        index.index_into(self)
+    }
+
+    /// Look up a key in an object, ignoring ASCII case, as for HTTP header names. If several
+    /// keys match, such as `"Content-Type"` and `"content-type"`, the first in the object is used.
+    ///
+    /// ```
+    /// use jsnom::json;
+    ///
+    /// let headers = json!({"Content-Type": "text/plain", "content-type": "text/html"});
+    /// assert_eq!(headers.get_ci("CONTENT-TYPE"), Some(&json!("text/plain")));
+    /// assert_eq!(headers.get_ci("accept"), None);
+    /// ```
+    pub fn get_ci(&self, key: &str) -> Option<&JsonValue> {
+        self.as_object()?
+            .iter()
+            .find(|(k, _)| k.eq_ignore_ascii_case(key))
+            .map(|(_, v)| v)
+    }
+
+    /// Mutable version of [`JsonValue::get`].
+    ///
+    /// ```
+    /// use jsnom::json;
+    ///
+    /// let mut value = json!({"retries": 3});
+    /// if let Some(retries) = value.get_mut("retries") {
+    ///     *retries = json!(5);
+    /// }
+    /// assert_eq!(value, json!({"retries": 5}));
+    /// ```
+    pub fn get_mut<I: JsonIndex>(&mut self, index: I) -> Option<&mut JsonValue> {
+        index.index_into_mut(self)
+    }
+
+    /// Returns `true` if the value is [`JsonValue::Null`].
+    ///
+    /// ```
+    /// use jsnom::JsonValue;
+    ///
+    /// assert!(JsonValue::Null.is_null());
+    /// assert!(!JsonValue::Bool(false).is_null());
+    /// ```
+    pub fn is_null(&self) -> bool {
+        matches!(self, JsonValue::Null)
+    }
+
+    /// Returns the inner `bool` if the value is a [`JsonValue::Bool`].
+    ///
+    /// ```
+    /// use jsnom::JsonValue;
+    ///
+    /// assert_eq!(JsonValue::Bool(true).as_bool(), Some(true));
+    /// assert_eq!(JsonValue::Null.as_bool(), None);
+    /// ```
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            JsonValue::Bool(b) => Some(*b),
+            _ => None,
+        }
+    }
+
+    /// Returns the inner string if the value is a [`JsonValue::String`].
+    ///
+    /// ```
+    /// use jsnom::JsonValue;
+    ///
+    /// assert_eq!(JsonValue::String("jsnom".to_string()).as_str(), Some("jsnom"));
+    /// assert_eq!(JsonValue::Null.as_str(), None);
+    /// ```
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            JsonValue::String(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    /// Returns the inner [`Number`] if the value is a [`JsonValue::Number`].
+    pub fn as_number(&self) -> Option<&Number> {
+        match self {
+            JsonValue::Number(n) => Some(n),
+            _ => None,
+        }
+    }
+
+    /// Returns the number as an `f64` if the value is a [`JsonValue::Number`].
+    ///
+    /// ```
+    /// use jsnom::JsonValue;
+    ///
+    /// assert_eq!(JsonValue::Number(1.5.into()).as_f64(), Some(1.5));
+    /// assert_eq!(JsonValue::Null.as_f64(), None);
+    /// ```
+    pub fn as_f64(&self) -> Option<f64> {
+        self.as_number().map(Number::as_f64)
+    }
+
+    /// Returns the number as an `i64` if the value is an integer [`JsonValue::Number`] in range.
+    ///
+    /// Integer literals are parsed straight into an integer rather than going through `f64`, so
+    /// this is exact even beyond 2^53, as for 64-bit IDs. A number written as a float, such as
+    /// `1.0` or `1e2`, gives `None`.
+    ///
+    /// ```
+    /// use jsnom::JsonValue;
+    ///
+    /// assert_eq!(JsonValue::Number((-2i64).into()).as_i64(), Some(-2));
+    /// assert_eq!(JsonValue::Number(1.5.into()).as_i64(), None);
+    ///
+    /// let id = jsnom::parse("{\"id\": 1234567890123456789}").unwrap();
+    /// assert_eq!(id["id"].as_i64(), Some(1234567890123456789));
+    /// ```
+    pub fn as_i64(&self) -> Option<i64> {
+        self.as_number().and_then(Number::as_i64)
+    }
+
+    /// Returns the number as a `u64` if the value is a non-negative integer
+    /// [`JsonValue::Number`] in range.
+    ///
+    /// ```
+    /// use jsnom::JsonValue;
+    ///
+    /// assert_eq!(JsonValue::Number(2u64.into()).as_u64(), Some(2));
+    /// assert_eq!(JsonValue::Number((-2i64).into()).as_u64(), None);
+    /// ```
+    pub fn as_u64(&self) -> Option<u64> {
+        self.as_number().and_then(Number::as_u64)
+    }
+
+    /// Returns the elements if the value is a [`JsonValue::Array`].
+    ///
+    /// ```
+    /// use jsnom::JsonValue;
+    ///
+    /// assert_eq!(
+    ///     JsonValue::Array(vec![JsonValue::Null]).as_array(),
+    ///     Some(&vec![JsonValue::Null])
+    /// );
+    /// assert_eq!(JsonValue::Null.as_array(), None);
+    /// ```
+    pub fn as_array(&self) -> Option<&Vec<JsonValue>> {
+        match self {
+            JsonValue::Array(items) => Some(items),
+            _ => None,
+        }
+    }
+
+    /// Returns the entries if the value is a [`JsonValue::Object`].
+    ///
+    /// ```
+    /// use jsnom::{JsonValue, Map};
+    ///
+    /// assert_eq!(JsonValue::Object(Map::new()).as_object(), Some(&Map::new()));
+    /// assert_eq!(JsonValue::Null.as_object(), None);
+    /// ```
+    pub fn as_object(&self) -> Option<&Map> {
+        match self {
+            JsonValue::Object(map) => Some(map),
+            _ => None,
+        }
+    }
+
+    /// Returns the number of elements of an array or entries of an object, or `None` for any
+    /// other value.
+    ///
+    /// ```
+    /// use jsnom::json;
+    ///
+    /// assert_eq!(json!([1, 2, 3]).len(), Some(3));
+    /// assert_eq!(json!({"a": 1}).len(), Some(1));
+    /// assert_eq!(json!("abc").len(), None);
+    /// ```
+    pub fn len(&self) -> Option<usize> {
+        match self {
+            JsonValue::Array(items) => Some(items.len()),
+            JsonValue::Object(map) => Some(map.len()),
+            _ => None,
+        }
+    }
+
+    /// Returns `true` if the value is an empty array or object. Any other value isn't a
+    /// collection, so isn't empty either.
+    ///
+    /// ```
+    /// use jsnom::json;
+    ///
+    /// assert!(json!([]).is_empty());
+    /// assert!(!json!({"a": 1}).is_empty());
+    /// assert!(!json!("").is_empty());
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        self.len() == Some(0)
+    }
+
+    /// Returns the keys, in order, if the value is a [`JsonValue::Object`].
+    ///
+    /// ```
+    /// use jsnom::{json, JsonValue};
+    ///
+    /// assert_eq!(json!({"b": 1, "a": 2}).keys(), Some(vec!["b", "a"]));
+    /// assert_eq!(json!([1]).keys(), None);
+    /// ```
+    pub fn keys(&self) -> Option<Vec<&str>> {
+        Some(self.as_object()?.keys().map(String::as_str).collect())
+    }
+
+    /// Checks that the value is an object with each of `keys`, returning the ones that are
+    /// missing, in the order given, if any are. Only the top level is checked. A value that isn't
+    /// an object is missing all of them.
+    ///
+    /// ```
+    /// use jsnom::json;
+    ///
+    /// let request = json!({"id": 1, "name": null});
+    /// assert_eq!(request.require_keys(&["id", "name"]), Ok(()));
+    /// assert_eq!(
+    ///     request.require_keys(&["id", "email", "role"]),
+    ///     Err(vec!["email".to_string(), "role".to_string()])
+    /// );
+    /// assert_eq!(json!([1]).require_keys(&["id"]), Err(vec!["id".to_string()]));
+    /// ```
+    pub fn require_keys(&self, keys: &[&str]) -> Result<(), Vec<String>> {
+        let object = self.as_object();
+        let missing: Vec<String> = keys
+            .iter()
+            .filter(|key| object.and_then(|object| object.get(key)).is_none())
+            .map(|key| String::from(*key))
+            .collect();
+        if missing.is_empty() {
+            Ok(())
+        } else {
+            Err(missing)
+        }
+    }
+
+    /// Takes the value out, leaving [`JsonValue::Null`] in its place.
+    ///
+    /// ```
+    /// use jsnom::JsonValue;
+    ///
+    /// let mut value = JsonValue::Bool(true);
+    /// assert_eq!(value.take(), JsonValue::Bool(true));
+    /// assert_eq!(value, JsonValue::Null);
+    /// ```
+    pub fn take(&mut self) -> JsonValue {
+        core::mem::replace(self, JsonValue::Null)
+    }
+
+    /// Returns the inner string if the value is a [`JsonValue::String`], without cloning it.
+    ///
+    /// ```
+    /// use jsnom::JsonValue;
+    ///
+    /// assert_eq!(
+    ///     JsonValue::String("jsnom".to_string()).into_string(),
+    ///     Some("jsnom".to_string())
+    /// );
+    /// assert_eq!(JsonValue::Null.into_string(), None);
+    /// ```
+    pub fn into_string(self) -> Option<String> {
+        match self {
+            JsonValue::String(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    /// Returns the elements if the value is a [`JsonValue::Array`], without cloning them.
+    ///
+    /// ```
+    /// use jsnom::JsonValue;
+    ///
+    /// assert_eq!(
+    ///     JsonValue::Array(vec![JsonValue::Null]).into_array(),
+    ///     Some(vec![JsonValue::Null])
+    /// );
+    /// assert_eq!(JsonValue::Null.into_array(), None);
+    /// ```
+    pub fn into_array(self) -> Option<Vec<JsonValue>> {
+        match self {
+            JsonValue::Array(items) => Some(items),
+            _ => None,
+        }
+    }
+
+    /// Returns the entries if the value is a [`JsonValue::Object`], without cloning them.
+    ///
+    /// ```
+    /// use jsnom::{JsonValue, Map};
+    ///
+    /// assert_eq!(JsonValue::Object(Map::new()).into_object(), Some(Map::new()));
+    /// assert_eq!(JsonValue::Null.into_object(), None);
+    /// ```
+    pub fn into_object(self) -> Option<Map> {
+        match self {
+            JsonValue::Object(map) => Some(map),
+            _ => None,
+        }
+    }
+
+    /// Look up a value by [RFC 6901](https://datatracker.ietf.org/doc/html/rfc6901) JSON Pointer,
+    /// such as `/users/0/name`. Returns `None` if any segment is missing or the value is of the
+    /// wrong type. The empty pointer `""` refers to the whole value.
+    ///
+    /// ```
+    /// use jsnom::JsonValue;
+    ///
+    /// let value = JsonValue::from_str("{\"users\": [{\"name\": \"Piturnah\"}], \"a/b\": 1}").unwrap();
+    /// assert_eq!(value.pointer("/users/0/name").and_then(JsonValue::as_str), Some("Piturnah"));
+    /// assert_eq!(value.pointer("/a~1b").and_then(JsonValue::as_u64), Some(1));
+    /// assert_eq!(value.pointer("/users/1"), None);
+    /// ```
+    pub fn pointer(&self, ptr: &str) -> Option<&JsonValue> {
+        pointer_segments(ptr)?.try_fold(self, |value, segment| match value {
+            JsonValue::Object(map) => map.get(&segment),
+            JsonValue::Array(items) => items.get(array_index(&segment)?),
+            _ => None,
+        })
+    }
+
+    /// Mutable version of [`JsonValue::pointer`], for editing a nested value in place.
+    ///
+    /// ```
+    /// use jsnom::json;
+    ///
+    /// let mut config = json!({"features": {"cache": {"enabled": false}}});
+    /// *config.pointer_mut("/features/cache/enabled").unwrap() = json!(true);
+    /// assert_eq!(config.to_string(), r#"{"features":{"cache":{"enabled":true}}}"#);
+    /// ```
+    pub fn pointer_mut(&mut self, ptr: &str) -> Option<&mut JsonValue> {
+        pointer_segments(ptr)?.try_fold(self, |value, segment| match value {
+            JsonValue::Object(map) => map.get_mut(&segment),
+            JsonValue::Array(items) => items.get_mut(array_index(&segment)?),
+            _ => None,
+        })
+    }
+
+    /// Returns an iterator over the elements of an array, which is empty for any other value.
+    ///
+    /// ```
+    /// use jsnom::JsonValue;
+    ///
+    /// let value = JsonValue::from_str("[1, 2, 3]").unwrap();
+    /// assert_eq!(value.array_iter().count(), 3);
+    /// assert_eq!(JsonValue::Null.array_iter().count(), 0);
+    /// ```
+    pub fn array_iter(&self) -> impl Iterator<Item = &JsonValue> {
+        self.as_array().into_iter().flatten()
+    }
+
+    /// Returns an iterator over the entries of an object, which is empty for any other value.
+    ///
+    /// ```
+    /// use jsnom::JsonValue;
+    ///
+    /// let value = JsonValue::from_str("{\"a\": null, \"b\": true}").unwrap();
+    /// let keys: Vec<&str> = value.object_iter().map(|(k, _)| k).collect();
+    /// assert_eq!(keys, ["a", "b"]);
+    /// ```
+    pub fn object_iter(&self) -> impl Iterator<Item = (&str, &JsonValue)> {
+        self.as_object()
+            .into_iter()
+            .flat_map(|map| map.iter().map(|(k, v)| (k.as_str(), v)))
+    }
+}
+
+// Splits a JSON Pointer into its unescaped reference tokens, or `None` if it is malformed.
+fn pointer_segments(ptr: &str) -> Option<impl Iterator<Item = String> + '_> {
+    if !ptr.is_empty() && !ptr.starts_with('/') {
+        return None;
+    }
+    Some(
+        ptr.split('/')
+            .skip(1)
+            .map(|segment| segment.replace("~1", "/").replace("~0", "~")),
+    )
+}
+
+// Array indices in a JSON Pointer are plain decimal, with no sign or leading zeros.
+pub(crate) fn array_index(segment: &str) -> Option<usize> {
+    if segment.starts_with('+') || (segment.starts_with('0') && segment.len() > 1) {
+        return None;
+    }
+    segment.parse().ok()
+}
+
+/// Iterates over the elements of an array. Any other value yields nothing.
+///
+/// ```
+/// use jsnom::JsonValue;
+///
+/// let value = JsonValue::from_str("[\"a\", \"b\"]").unwrap();
+/// for item in value {
+///     assert!(item.as_str().is_some());
+/// }
+/// ```
+impl IntoIterator for JsonValue {
+    type Item = JsonValue;
+    type IntoIter = alloc::vec::IntoIter<JsonValue>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        match self {
+            JsonValue::Array(items) => items.into_iter(),
+            _ => Vec::new().into_iter(),
+        }
+    }
+}
+
+/// Look up the value of a key in a [`JsonValue::Object`]. If the key appears more than once, the
+/// last entry is used.
+///
+/// # Panics
+///
+/// Panics if the value is not an object or the key is missing.
+///
+/// ```
+/// use jsnom::JsonValue;
+///
+/// let value = JsonValue::from_str("{\"name\": \"jsnom\"}").unwrap();
+/// assert_eq!(value["name"], JsonValue::String("jsnom".to_string()));
+/// ```
+impl Index<&str> for JsonValue {
+    type Output = JsonValue;
+
+    fn index(&self, key: &str) -> &JsonValue {
+        match self {
+            JsonValue::Object(_) => self
+                .get(key)
+                .unwrap_or_else(|| panic!("key {key:?} not found in object")),
+            _ => panic!("cannot index into a non-object value with key {key:?}"),
+        }
+    }
+}
+
+/// Look up an element of a [`JsonValue::Array`].
+///
+/// # Panics
+///
+/// Panics if the value is not an array or the index is out of bounds.
+///
+/// ```
+/// use jsnom::JsonValue;
+///
+/// let value = JsonValue::from_str("[null, true]").unwrap();
+/// assert_eq!(value[1], JsonValue::Bool(true));
+/// ```
+impl Index<usize> for JsonValue {
+    type Output = JsonValue;
+
+    fn index(&self, index: usize) -> &JsonValue {
+        match self {
+            JsonValue::Array(items) => &items[index],
+            _ => panic!("cannot index into a non-array value with index {index}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{JsonValue, Map};
+
+    #[test]
+    fn get_wrong_type() {
+        assert_eq!(JsonValue::Array(vec![JsonValue::Null]).get("0"), None);
+        assert_eq!(JsonValue::Object(Map::new()).get(0), None);
+    }
+
+    #[test]
+    fn get_ci() {
+        let value = json!({"Ä": 1, "a": 2});
+        assert_eq!(value.get_ci("A"), Some(&json!(2)));
+        assert_eq!(value.get_ci("ä"), None);
+        assert_eq!(json!(["a"]).get_ci("a"), None);
+    }
+
+    #[test]
+    fn pointer() {
+        let value = json!({"a": [0, {"b": true}], "m~n": 1, "": 2});
+        assert_eq!(value.pointer(""), Some(&value));
+        assert_eq!(value.pointer("/a/1/b"), Some(&JsonValue::Bool(true)));
+        assert_eq!(value.pointer("/m~0n"), Some(&json!(1)));
+        assert_eq!(value.pointer("/"), Some(&json!(2)));
+        assert_eq!(value.pointer("a"), None);
+        assert_eq!(value.pointer("/a/01"), None);
+        assert_eq!(value.pointer("/a/-"), None);
+        assert_eq!(value.pointer("/a/0/b"), None);
+    }
+
+    #[test]
+    fn pointer_mut() {
+        let mut value = json!({"a": [0, {"b": true}]});
+        value.pointer_mut("/a/1/b").unwrap().take();
+        *value.pointer_mut("/a/0").unwrap() = json!("x");
+        assert_eq!(value, json!({"a": ["x", {"b": null}]}));
+        assert_eq!(value.pointer_mut("/a/2"), None);
+        assert_eq!(value.pointer_mut("/a/0/b"), None);
+        assert!(value.get_mut("a").and_then(|a| a.get_mut(1)).is_some());
+        assert_eq!(value.get_mut(0), None);
+    }
+
+    #[test]
+    #[should_panic]
+    fn index_missing_key() {
+        let _ = &JsonValue::Object(Map::new())["missing"];
+    }
+
+    #[test]
+    #[should_panic]
+    fn index_out_of_bounds() {
+        let _ = &JsonValue::Array(Vec::new())[0];
+    }
+}
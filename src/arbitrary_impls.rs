@@ -0,0 +1,82 @@
+use arbitrary::{Arbitrary, Result, Unstructured};
+
+use crate::{JsonValue, Map, Number};
+
+/// How deeply generated arrays and objects may nest, so that generation always terminates.
+const MAX_DEPTH: usize = 8;
+
+/// Generates an integer or a finite float, since JSON can't represent `NaN` or infinities.
+impl<'a> Arbitrary<'a> for Number {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(match u.choose_index(3)? {
+            0 => Number::from(u64::arbitrary(u)?),
+            1 => Number::from(i64::arbitrary(u)?),
+            _ => {
+                let n = f64::arbitrary(u)?;
+                Number::from(if n.is_finite() { n } else { 0.0 })
+            }
+        })
+    }
+}
+
+/// Generates trees of arrays and objects nested at most eight deep.
+impl<'a> Arbitrary<'a> for JsonValue {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        arbitrary_value(u, MAX_DEPTH)
+    }
+
+    fn size_hint(_depth: usize) -> (usize, Option<usize>) {
+        (1, None)
+    }
+}
+
+fn arbitrary_value(u: &mut Unstructured<'_>, depth: usize) -> Result<JsonValue> {
+    // Only scalars are possible once the depth runs out.
+    let kinds = if depth == 0 { 4 } else { 6 };
+    Ok(match u.choose_index(kinds)? {
+        0 => JsonValue::Null,
+        1 => JsonValue::Bool(u.arbitrary()?),
+        2 => JsonValue::String(u.arbitrary()?),
+        3 => JsonValue::Number(u.arbitrary()?),
+        4 => {
+            let len = u.arbitrary_len::<JsonValue>()?;
+            JsonValue::Array(
+                (0..len)
+                    .map(|_| arbitrary_value(u, depth - 1))
+                    .collect::<Result<_>>()?,
+            )
+        }
+        _ => {
+            let len = u.arbitrary_len::<(String, JsonValue)>()?;
+            JsonValue::Object(
+                (0..len)
+                    .map(|_| Ok((u.arbitrary()?, arbitrary_value(u, depth - 1)?)))
+                    .collect::<Result<Map>>()?,
+            )
+        }
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use arbitrary::{Arbitrary, Unstructured};
+
+    use crate::JsonValue;
+
+    #[test]
+    fn arbitrary_is_bounded() {
+        let data: Vec<u8> = (0..4096u32)
+            .map(|i| (i.wrapping_mul(2654435761) >> 13) as u8)
+            .collect();
+        let mut u = Unstructured::new(&data);
+        while !u.is_empty() {
+            let value = JsonValue::arbitrary(&mut u).unwrap();
+            for (path, v) in value.descendants_with_path() {
+                assert!(path.len() <= super::MAX_DEPTH);
+                if let Some(n) = v.as_f64() {
+                    assert!(n.is_finite());
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,160 @@
+//! Parsing into a [`bumpalo::Bump`] arena.
+
+use alloc::{string::String, vec::Vec};
+
+use bumpalo::{collections::Vec as BumpVec, Bump};
+
+use crate::{parse::Event, JsonValue, Map, Number};
+
+/// A parsed JSON value whose strings, arrays and objects are allocated in a [`Bump`] arena, as
+/// returned by [`parse_in`](crate::parse_in). Everything it holds is freed at once when the
+/// arena is reset or dropped, which saves work when many small documents are parsed.
+///
+/// ```
+/// use bumpalo::Bump;
+/// use jsnom::{parse_in, JsonValueIn};
+///
+/// let mut bump = Bump::new();
+/// for body in ["{\"id\": 1}", "{\"id\": 2}"] {
+///     {
+///         let value = parse_in(body, &bump).unwrap();
+///         assert!(matches!(value, JsonValueIn::Object(entries) if entries[0].0 == "id"));
+///     }
+///     bump.reset();
+/// }
+/// ```
+#[derive(Debug, PartialEq)]
+pub enum JsonValueIn<'b> {
+    Null,
+    Bool(bool),
+    String(&'b str),
+    Array(BumpVec<'b, JsonValueIn<'b>>),
+    Number(Number),
+    Object(BumpVec<'b, (&'b str, JsonValueIn<'b>)>),
+}
+
+impl JsonValueIn<'_> {
+    /// Convert into a [`JsonValue`], copying everything out of the arena.
+    ///
+    /// ```
+    /// use bumpalo::Bump;
+    /// use jsnom::{parse, parse_in};
+    ///
+    /// let bump = Bump::new();
+    /// let s = "{\"a\": [1, \"b\"]}";
+    /// assert_eq!(parse_in(s, &bump).unwrap().to_owned(), parse(s).unwrap());
+    /// ```
+    pub fn to_owned(&self) -> JsonValue {
+        match self {
+            JsonValueIn::Null => JsonValue::Null,
+            JsonValueIn::Bool(b) => JsonValue::Bool(*b),
+            JsonValueIn::String(s) => JsonValue::String(String::from(*s)),
+            JsonValueIn::Array(items) => {
+                JsonValue::Array(items.iter().map(Self::to_owned).collect())
+            }
+            JsonValueIn::Number(n) => JsonValue::Number(n.clone()),
+            JsonValueIn::Object(entries) => JsonValue::Object(Map::from(
+                entries
+                    .iter()
+                    .map(|(key, value)| (String::from(*key), value.to_owned()))
+                    .collect::<Vec<_>>(),
+            )),
+        }
+    }
+}
+
+// Builds a `JsonValueIn` from the events of `Context::events`. The elements and entries of
+// unfinished arrays and objects are kept on the heap, innermost last, and only moved into the
+// arena once complete, so that each is allocated there once at its final size.
+pub(crate) struct Builder<'b> {
+    bump: &'b Bump,
+    elements: Vec<JsonValueIn<'b>>,
+    entries: Vec<(&'b str, JsonValueIn<'b>)>,
+    // Where each unfinished array or object starts in `elements` or `entries`, along with the key
+    // for the next value of an object.
+    stack: Vec<Partial<'b>>,
+    pub(crate) value: Option<JsonValueIn<'b>>,
+}
+
+enum Partial<'b> {
+    Array(usize),
+    Object(usize, &'b str),
+}
+
+impl<'b> Builder<'b> {
+    pub(crate) fn new(bump: &'b Bump) -> Self {
+        Self {
+            bump,
+            elements: Vec::new(),
+            entries: Vec::new(),
+            stack: Vec::new(),
+            value: None,
+        }
+    }
+
+    pub(crate) fn event(&mut self, event: Event) {
+        let value = match event {
+            Event::Null => JsonValueIn::Null,
+            Event::Bool(b) => JsonValueIn::Bool(b),
+            Event::String(s) => JsonValueIn::String(self.bump.alloc_str(&s)),
+            Event::Number(n) => JsonValueIn::Number(n),
+            Event::BeginArray => return self.stack.push(Partial::Array(self.elements.len())),
+            Event::BeginObject => return self.stack.push(Partial::Object(self.entries.len(), "")),
+            Event::Key(key) => {
+                let key = self.bump.alloc_str(&key);
+                if let Some(Partial::Object(_, next)) = self.stack.last_mut() {
+                    *next = key;
+                }
+                return;
+            }
+            Event::EndArray | Event::EndObject => match self.stack.pop().unwrap() {
+                Partial::Array(start) => {
+                    JsonValueIn::Array(finish(self.bump, &mut self.elements, start))
+                }
+                Partial::Object(start, _) => {
+                    JsonValueIn::Object(finish(self.bump, &mut self.entries, start))
+                }
+            },
+        };
+        match self.stack.last_mut() {
+            None => self.value = Some(value),
+            Some(Partial::Array(_)) => self.elements.push(value),
+            Some(Partial::Object(_, key)) => self.entries.push((key, value)),
+        }
+    }
+}
+
+// Moves everything from `start` onwards into the arena.
+fn finish<'b, T>(bump: &'b Bump, pending: &mut Vec<T>, start: usize) -> BumpVec<'b, T> {
+    let mut items = BumpVec::with_capacity_in(pending.len() - start, bump);
+    items.extend(pending.drain(start..));
+    items
+}
+
+#[cfg(test)]
+mod test {
+    use bumpalo::Bump;
+
+    use super::JsonValueIn;
+    use crate::{parse, parse_in};
+
+    #[test]
+    fn parse_in_arena() {
+        let s = r#"{"a": [1, {"b": [], "c": "x\ny"}, [null, true]], "d": {}}"#;
+        let bump = Bump::new();
+        let value = parse_in(s, &bump).unwrap();
+        assert_eq!(value.to_owned(), parse(s).unwrap());
+
+        let JsonValueIn::Object(entries) = &value else {
+            panic!("expected an object");
+        };
+        let JsonValueIn::Array(items) = &entries[0].1 else {
+            panic!("expected an array");
+        };
+        // Moved into the arena once complete, at exactly their final size.
+        assert_eq!(entries.capacity(), 2);
+        assert_eq!(items.capacity(), 3);
+        assert!(bump.allocated_bytes() > 0);
+        assert!(parse_in("[1, ?]", &bump).is_err());
+    }
+}
@@ -0,0 +1,121 @@
+//! Conversions between [`Number`] and [`num_bigint::BigInt`].
+
+use alloc::string::ToString;
+
+use num_bigint::BigInt;
+
+use crate::{parse, JsonValue, Number};
+
+impl Number {
+    /// Returns the number as a [`BigInt`] if it is an integer, however large.
+    ///
+    /// With the `num-bigint` feature, an integer literal too large for a `u64` or `i64` keeps the
+    /// text it was written as alongside its `f64` approximation, so that its digits aren't lost.
+    /// They are also what gets written back out. Floats, including whole ones such as `1.0` or
+    /// `1e3`, give `None`, as for [`Number::as_i64`].
+    ///
+    /// ```
+    /// use jsnom::parse;
+    /// use num_bigint::BigInt;
+    ///
+    /// let value = parse("[123456789012345678901234567890, 1.5]").unwrap();
+    /// assert_eq!(
+    ///     value[0].as_bigint(),
+    ///     "123456789012345678901234567890".parse::<BigInt>().ok()
+    /// );
+    /// assert_eq!(value.to_string(), "[123456789012345678901234567890,1.5]");
+    /// assert_eq!(value[1].as_bigint(), None);
+    /// ```
+    pub fn as_bigint(&self) -> Option<BigInt> {
+        if let Some(n) = self.as_u64() {
+            return Some(BigInt::from(n));
+        }
+        if let Some(n) = self.as_i64() {
+            return Some(BigInt::from(n));
+        }
+        let literal = self.literal()?;
+        if literal.contains(['.', 'e', 'E']) {
+            return None;
+        }
+        literal.parse().ok()
+    }
+}
+
+impl JsonValue {
+    /// Returns the number as a [`BigInt`] if the value is a [`JsonValue::Number`] holding an
+    /// integer. See [`Number::as_bigint`].
+    pub fn as_bigint(&self) -> Option<BigInt> {
+        self.as_number()?.as_bigint()
+    }
+}
+
+/// Converts a [`BigInt`] exactly, stored as a `u64` or `i64` if it fits, and otherwise keeping
+/// its digits as the number's literal.
+///
+/// ```
+/// use jsnom::JsonValue;
+/// use num_bigint::BigInt;
+///
+/// let n = BigInt::from(u64::MAX) * 1000u32;
+/// let value = JsonValue::from(n.clone());
+/// assert_eq!(value.to_string(), "18446744073709551615000");
+/// assert_eq!(value.as_bigint(), Some(n));
+/// ```
+impl From<BigInt> for Number {
+    fn from(n: BigInt) -> Self {
+        if let Ok(n) = u64::try_from(&n) {
+            return Number::from(n);
+        }
+        if let Ok(n) = i64::try_from(&n) {
+            return Number::from(n);
+        }
+        let literal = n.to_string();
+        match parse::nom_number(&literal) {
+            Ok((_, JsonValue::Number(n))) => n.with_literal(&literal),
+            // Too large even for an `f64`.
+            _ => Number::from(if n.sign() == num_bigint::Sign::Minus {
+                f64::NEG_INFINITY
+            } else {
+                f64::INFINITY
+            })
+            .with_literal(&literal),
+        }
+    }
+}
+
+impl From<BigInt> for JsonValue {
+    fn from(n: BigInt) -> Self {
+        JsonValue::Number(Number::from(n))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use num_bigint::BigInt;
+
+    use crate::{parse, JsonValue};
+
+    #[test]
+    fn as_bigint() {
+        let big = "-98765432109876543210987654321";
+        let value = parse(&format!("[{big}, 18446744073709551616, -0, 7, 1e3]")).unwrap();
+        assert_eq!(value[0].as_bigint(), big.parse::<BigInt>().ok());
+        assert_eq!(value[1].as_bigint(), Some(BigInt::from(u64::MAX) + 1));
+        assert_eq!(value[2].as_bigint(), None);
+        assert_eq!(value[3].as_bigint(), Some(BigInt::from(7)));
+        assert_eq!(value[4].as_bigint(), None);
+        assert_eq!(
+            value.to_string(),
+            format!("[{big},18446744073709551616,-0,7,1000]")
+        );
+    }
+
+    #[test]
+    fn from_bigint() {
+        assert_eq!(JsonValue::from(BigInt::from(-5)), JsonValue::from(-5));
+        let huge = BigInt::from(10).pow(400);
+        let value = JsonValue::from(huge.clone());
+        assert_eq!(value.as_bigint(), Some(huge));
+        assert_eq!(value.to_string().len(), 401);
+    }
+}
@@ -0,0 +1,109 @@
+use alloc::{borrow::Cow, vec::Vec};
+
+use crate::{parse::Event, JsonValue, Map, Number};
+
+/// A parsed JSON value whose strings borrow from the input, as returned by
+/// [`parse_borrowed`](crate::parse_borrowed). Only strings with escapes in them have to be
+/// allocated, since their decoded text doesn't appear in the input.
+///
+/// ```
+/// use std::borrow::Cow;
+///
+/// use jsnom::{parse_borrowed, JsonValueRef};
+///
+/// let value = parse_borrowed("[\"plain\", \"escaped\\n\"]").unwrap();
+/// let JsonValueRef::Array(items) = &value else {
+///     panic!("expected an array");
+/// };
+/// assert!(matches!(items[0], JsonValueRef::String(Cow::Borrowed("plain"))));
+/// assert!(matches!(items[1], JsonValueRef::String(Cow::Owned(_))));
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub enum JsonValueRef<'a> {
+    Null,
+    Bool(bool),
+    String(Cow<'a, str>),
+    Array(Vec<JsonValueRef<'a>>),
+    Number(Number),
+    Object(Vec<(Cow<'a, str>, JsonValueRef<'a>)>),
+}
+
+impl JsonValueRef<'_> {
+    /// Convert into a [`JsonValue`], copying any borrowed strings.
+    ///
+    /// ```
+    /// use jsnom::{parse, parse_borrowed};
+    ///
+    /// let s = "{\"a\": [1, \"b\"]}";
+    /// assert_eq!(parse_borrowed(s).unwrap().into_owned(), parse(s).unwrap());
+    /// ```
+    pub fn into_owned(self) -> JsonValue {
+        match self {
+            JsonValueRef::Null => JsonValue::Null,
+            JsonValueRef::Bool(b) => JsonValue::Bool(b),
+            JsonValueRef::String(s) => JsonValue::String(s.into_owned()),
+            JsonValueRef::Array(items) => {
+                JsonValue::Array(items.into_iter().map(Self::into_owned).collect())
+            }
+            JsonValueRef::Number(n) => JsonValue::Number(n),
+            JsonValueRef::Object(entries) => JsonValue::Object(Map::from(
+                entries
+                    .into_iter()
+                    .map(|(key, value)| (key.into_owned(), value.into_owned()))
+                    .collect::<Vec<_>>(),
+            )),
+        }
+    }
+}
+
+impl From<JsonValueRef<'_>> for JsonValue {
+    fn from(value: JsonValueRef<'_>) -> Self {
+        value.into_owned()
+    }
+}
+
+// Builds a `JsonValueRef` from the events of `Context::events`.
+#[derive(Default)]
+pub(crate) struct Builder<'a> {
+    stack: Vec<Partial<'a>>,
+    pub(crate) value: Option<JsonValueRef<'a>>,
+}
+
+// An array or object that a `Builder` is part way through, along with the key for the next value
+// of an object.
+enum Partial<'a> {
+    Array(Vec<JsonValueRef<'a>>),
+    Object(Vec<(Cow<'a, str>, JsonValueRef<'a>)>, Cow<'a, str>),
+}
+
+impl<'a> Builder<'a> {
+    pub(crate) fn event(&mut self, event: Event<'a>) {
+        let value = match event {
+            Event::Null => JsonValueRef::Null,
+            Event::Bool(b) => JsonValueRef::Bool(b),
+            Event::String(s) => JsonValueRef::String(s),
+            Event::Number(n) => JsonValueRef::Number(n),
+            Event::BeginArray => return self.stack.push(Partial::Array(Vec::new())),
+            Event::BeginObject => {
+                return self
+                    .stack
+                    .push(Partial::Object(Vec::new(), Cow::Borrowed("")));
+            }
+            Event::Key(key) => {
+                if let Some(Partial::Object(_, next)) = self.stack.last_mut() {
+                    *next = key;
+                }
+                return;
+            }
+            Event::EndArray | Event::EndObject => match self.stack.pop().unwrap() {
+                Partial::Array(items) => JsonValueRef::Array(items),
+                Partial::Object(entries, _) => JsonValueRef::Object(entries),
+            },
+        };
+        match self.stack.last_mut() {
+            None => self.value = Some(value),
+            Some(Partial::Array(items)) => items.push(value),
+            Some(Partial::Object(entries, key)) => entries.push((core::mem::take(key), value)),
+        }
+    }
+}
@@ -0,0 +1,95 @@
+use alloc::{string::String, vec::Vec};
+
+use crate::{JsonValue, Map, Number};
+
+/// Builds a [`JsonValue::Object`] one entry at a time. Created with [`JsonValue::object`].
+#[derive(Clone, Debug, Default)]
+#[must_use = "call `build` to get the finished object"]
+pub struct ObjectBuilder {
+    map: Map,
+}
+
+impl ObjectBuilder {
+    /// Adds an entry, replacing the value of any earlier entry with the same key.
+    pub fn insert(mut self, key: impl Into<String>, value: impl Into<JsonValue>) -> Self {
+        self.map.insert(key.into(), value.into());
+        self
+    }
+
+    /// Finishes the object.
+    pub fn build(self) -> JsonValue {
+        JsonValue::Object(self.map)
+    }
+}
+
+/// Builds a [`JsonValue::Array`] one element at a time. Created with [`JsonValue::array`].
+#[derive(Clone, Debug, Default)]
+#[must_use = "call `build` to get the finished array"]
+pub struct ArrayBuilder {
+    items: Vec<JsonValue>,
+}
+
+impl ArrayBuilder {
+    /// Adds an element to the end of the array.
+    pub fn push(mut self, value: impl Into<JsonValue>) -> Self {
+        self.items.push(value.into());
+        self
+    }
+
+    /// Finishes the array.
+    pub fn build(self) -> JsonValue {
+        JsonValue::Array(self.items)
+    }
+}
+
+impl JsonValue {
+    /// Start building an object, for when [`json!`](crate::json) doesn't fit, such as when the
+    /// entries are decided at runtime.
+    ///
+    /// ```
+    /// use jsnom::JsonValue;
+    ///
+    /// let response = JsonValue::object()
+    ///     .insert("ok", true)
+    ///     .insert("count", 3)
+    ///     .insert("items", JsonValue::array().push("a").push(JsonValue::Null).build())
+    ///     .build();
+    /// assert_eq!(response.to_string(), r#"{"ok":true,"count":3,"items":["a",null]}"#);
+    /// ```
+    pub fn object() -> ObjectBuilder {
+        ObjectBuilder::default()
+    }
+
+    /// Start building an array. See [`JsonValue::object`].
+    pub fn array() -> ArrayBuilder {
+        ArrayBuilder::default()
+    }
+
+    /// Makes a [`JsonValue::String`].
+    ///
+    /// ```
+    /// use jsnom::JsonValue;
+    ///
+    /// let tags = JsonValue::array()
+    ///     .push(JsonValue::string("json"))
+    ///     .push(JsonValue::number(2))
+    ///     .push(JsonValue::number(0.5))
+    ///     .push(JsonValue::bool(false))
+    ///     .build();
+    /// assert_eq!(tags.to_string(), r#"["json",2,0.5,false]"#);
+    /// ```
+    pub fn string(s: impl Into<String>) -> Self {
+        JsonValue::String(s.into())
+    }
+
+    /// Makes a [`JsonValue::Number`] from any Rust integer or float. Integers are kept exact
+    /// rather than converted to an `f64`.
+    pub fn number(n: impl Into<Number>) -> Self {
+        JsonValue::Number(n.into())
+    }
+
+    /// Makes a [`JsonValue::Bool`].
+    pub fn bool(b: bool) -> Self {
+        JsonValue::Bool(b)
+    }
+}
@@ -0,0 +1,367 @@
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::{cmp::Ordering, fmt};
+
+use crate::JsonValue;
+
+/// A single difference between two values, as returned by [`JsonValue::diff`].
+///
+/// Each variant carries the [RFC 6901](https://datatracker.ietf.org/doc/html/rfc6901) JSON Pointer
+/// to the value that differs, so it can be passed straight back to [`JsonValue::pointer`].
+#[derive(Clone, Debug, PartialEq)]
+pub enum Difference {
+    /// A value present only in the second tree.
+    Added { path: String, value: JsonValue },
+    /// A value present only in the first tree.
+    Removed { path: String, value: JsonValue },
+    /// A value present in both trees with different contents.
+    Changed {
+        path: String,
+        before: JsonValue,
+        after: JsonValue,
+    },
+}
+
+impl Difference {
+    /// The JSON Pointer to the value that differs.
+    pub fn path(&self) -> &str {
+        match self {
+            Difference::Added { path, .. }
+            | Difference::Removed { path, .. }
+            | Difference::Changed { path, .. } => path,
+        }
+    }
+}
+
+impl fmt::Display for Difference {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Difference::Added { path, value } => write!(f, "{path}: added {value}"),
+            Difference::Removed { path, value } => write!(f, "{path}: removed {value}"),
+            Difference::Changed {
+                path,
+                before,
+                after,
+            } => write!(f, "{path}: {before} != {after}"),
+        }
+    }
+}
+
+impl JsonValue {
+    /// Compare two values, treating objects as unordered maps. Arrays are still compared in order,
+    /// and numbers must be equal as with [`PartialEq`].
+    ///
+    /// ```
+    /// use jsnom::JsonValue;
+    ///
+    /// let a = JsonValue::from_str("{\"x\": 1, \"y\": [{\"p\": 1, \"q\": 2}]}").unwrap();
+    /// let b = JsonValue::from_str("{\"y\": [{\"q\": 2, \"p\": 1}], \"x\": 1}").unwrap();
+    /// assert_ne!(a, b);
+    /// assert!(a.eq_unordered(&b));
+    /// ```
+    pub fn eq_unordered(&self, other: &JsonValue) -> bool {
+        match (self, other) {
+            (JsonValue::Array(a), JsonValue::Array(b)) => {
+                a.len() == b.len() && a.iter().zip(b).all(|(a, b)| a.eq_unordered(b))
+            }
+            (JsonValue::Object(a), JsonValue::Object(b)) => {
+                a.len() == b.len()
+                    && a.iter()
+                        .all(|(k, v)| b.get(k).is_some_and(|w| v.eq_unordered(w)))
+            }
+            (a, b) => a == b,
+        }
+    }
+
+    /// Compare two values like [`eq_unordered`](Self::eq_unordered), but treat numbers as equal
+    /// when they differ by at most `epsilon`, whether they were written as integers or floats.
+    ///
+    /// ```
+    /// use jsnom::JsonValue;
+    ///
+    /// let computed = JsonValue::from_str("{\"total\": 0.30000000000000004, \"n\": 3}").unwrap();
+    /// let expected = JsonValue::from_str("{\"n\": 3.0, \"total\": 0.3}").unwrap();
+    /// assert!(computed.approx_eq(&expected, 1e-9));
+    /// assert!(!computed.approx_eq(&expected, 0.0));
+    /// ```
+    pub fn approx_eq(&self, other: &JsonValue, epsilon: f64) -> bool {
+        match (self, other) {
+            (JsonValue::Number(a), JsonValue::Number(b)) => {
+                let difference = a.as_f64() - b.as_f64();
+                a == b || (difference <= epsilon && -difference <= epsilon)
+            }
+            (JsonValue::Array(a), JsonValue::Array(b)) => {
+                a.len() == b.len() && a.iter().zip(b).all(|(a, b)| a.approx_eq(b, epsilon))
+            }
+            (JsonValue::Object(a), JsonValue::Object(b)) => {
+                a.len() == b.len()
+                    && a.iter()
+                        .all(|(k, v)| b.get(k).is_some_and(|w| v.approx_eq(w, epsilon)))
+            }
+            (a, b) => a == b,
+        }
+    }
+
+    /// List the differences between `self` and `other`, treating objects as unordered maps.
+    /// Arrays are compared index by index, with any extra elements reported as added or removed.
+    ///
+    /// ```
+    /// use jsnom::{json, Difference};
+    ///
+    /// let before = json!({"config": {"timeout": 30, "retries": 3}, "tags": ["a"]});
+    /// let after = json!({"tags": ["a", "b"], "config": {"timeout": 60, "retries": 3}});
+    /// let diff = before.diff(&after);
+    /// assert_eq!(diff.len(), 2);
+    /// assert_eq!(diff[0].to_string(), "/config/timeout: 30 != 60");
+    /// assert_eq!(
+    ///     diff[1],
+    ///     Difference::Added { path: "/tags/1".to_string(), value: json!("b") }
+    /// );
+    /// ```
+    pub fn diff(&self, other: &JsonValue) -> Vec<Difference> {
+        let mut differences = Vec::new();
+        self.diff_into(other, &mut String::new(), &mut differences);
+        differences
+    }
+
+    fn diff_into(&self, other: &JsonValue, path: &mut String, out: &mut Vec<Difference>) {
+        let len = path.len();
+        match (self, other) {
+            (JsonValue::Array(a), JsonValue::Array(b)) => {
+                for (i, (a, b)) in a.iter().zip(b).enumerate() {
+                    push_segment(path, &i.to_string());
+                    a.diff_into(b, path, out);
+                    path.truncate(len);
+                }
+                for (i, value) in a.iter().enumerate().skip(b.len()) {
+                    out.push(Difference::Removed {
+                        path: format!("{path}/{i}"),
+                        value: value.clone(),
+                    });
+                }
+                for (i, value) in b.iter().enumerate().skip(a.len()) {
+                    out.push(Difference::Added {
+                        path: format!("{path}/{i}"),
+                        value: value.clone(),
+                    });
+                }
+            }
+            (JsonValue::Object(a), JsonValue::Object(b)) => {
+                for (key, value) in a {
+                    push_segment(path, key);
+                    match b.get(key) {
+                        Some(other) => value.diff_into(other, path, out),
+                        None => out.push(Difference::Removed {
+                            path: path.clone(),
+                            value: value.clone(),
+                        }),
+                    }
+                    path.truncate(len);
+                }
+                for (key, value) in b {
+                    if !a.contains_key(key) {
+                        push_segment(path, key);
+                        out.push(Difference::Added {
+                            path: path.clone(),
+                            value: value.clone(),
+                        });
+                        path.truncate(len);
+                    }
+                }
+            }
+            (a, b) if a == b => {}
+            (a, b) => out.push(Difference::Changed {
+                path: path.clone(),
+                before: a.clone(),
+                after: b.clone(),
+            }),
+        }
+    }
+}
+
+/// Values are ordered first by kind, with `null` < booleans < numbers < strings < arrays < objects,
+/// and then by their contents. Numbers are ordered as described on [`Number`](crate::Number),
+/// arrays element by element, and objects entry by entry in key order, comparing each key and
+/// then its value. The order agrees with [`Eq`], so objects with the same entries in a different
+/// order are unequal.
+///
+/// ```
+/// use jsnom::json;
+///
+/// let mut values = vec![json!({}), json!("b"), json!([1]), json!(2), json!(null), json!("a"), json!(false)];
+/// values.sort();
+/// assert_eq!(values, [json!(null), json!(false), json!(2), json!("a"), json!("b"), json!([1]), json!({})]);
+/// ```
+impl Ord for JsonValue {
+    fn cmp(&self, other: &Self) -> Ordering {
+        fn rank(value: &JsonValue) -> u8 {
+            match value {
+                JsonValue::Null => 0,
+                JsonValue::Bool(_) => 1,
+                JsonValue::Number(_) => 2,
+                JsonValue::String(_) => 3,
+                JsonValue::Array(_) => 4,
+                JsonValue::Object(_) => 5,
+            }
+        }
+        match (self, other) {
+            (JsonValue::Bool(a), JsonValue::Bool(b)) => a.cmp(b),
+            (JsonValue::Number(a), JsonValue::Number(b)) => a.cmp(b),
+            (JsonValue::String(a), JsonValue::String(b)) => a.cmp(b),
+            (JsonValue::Array(a), JsonValue::Array(b)) => a.cmp(b),
+            (JsonValue::Object(a), JsonValue::Object(b)) => a.cmp(b),
+            (a, b) => rank(a).cmp(&rank(b)),
+        }
+    }
+}
+
+impl PartialOrd for JsonValue {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Append `segment` to a JSON Pointer, escaping `~` and `/` as RFC 6901 requires.
+fn push_segment(path: &mut String, segment: &str) {
+    path.push('/');
+    path.push_str(&segment.replace('~', "~0").replace('/', "~1"));
+}
+
+#[cfg(test)]
+mod test {
+    #[test]
+    fn eq_unordered() {
+        assert!(json!({"a": 1, "b": 2}).eq_unordered(&json!({"b": 2, "a": 1})));
+        assert!(!json!({"a": 1, "b": 2}).eq_unordered(&json!({"a": 1})));
+        assert!(!json!({"a": 1}).eq_unordered(&json!({"a": 1, "b": 2})));
+        assert!(!json!([1, 2]).eq_unordered(&json!([2, 1])));
+        assert!(json!({"a": 1}).eq_unordered(&json!({"a": 1.0})));
+        assert!(!json!({"a": 1}).eq_unordered(&json!({"a": 1.5})));
+    }
+
+    #[test]
+    fn approx_eq() {
+        assert!(json!({"a": [1, 2.5]}).approx_eq(&json!({"a": [1.0, 2.5]}), 0.0));
+        assert!(json!([0.1, 0.2]).approx_eq(&json!([0.1000001, 0.2]), 1e-6));
+        assert!(!json!([0.1, 0.2]).approx_eq(&json!([0.1001, 0.2]), 1e-6));
+        assert!(!json!([1, 2]).approx_eq(&json!([2, 1]), 0.5));
+        assert!(!json!({"a": 1}).approx_eq(&json!({"a": "1"}), 1.0));
+        assert!(json!({"a": 1, "b": null}).approx_eq(&json!({"b": null, "a": 1.5}), 0.5));
+    }
+
+    #[test]
+    fn hash_agrees_with_eq() {
+        use std::{
+            collections::hash_map::DefaultHasher,
+            hash::{Hash, Hasher},
+        };
+
+        let hash = |value: &crate::JsonValue| {
+            let mut hasher = DefaultHasher::new();
+            value.hash(&mut hasher);
+            hasher.finish()
+        };
+        assert_eq!(hash(&json!(0.0)), hash(&json!(-0.0)));
+        assert_eq!(json!(0.0), json!(-0.0));
+        assert_eq!(
+            hash(&json!({"a": [1, "x"], "b": null})),
+            hash(&json!({"a": [1, "x"], "b": null}))
+        );
+        assert_eq!(hash(&json!(1)), hash(&json!(1.0)));
+        assert_eq!(
+            hash(&crate::JsonValue::from(f64::NAN)),
+            hash(&crate::JsonValue::from(-f64::NAN))
+        );
+        assert_eq!(hash(&json!(-2)), hash(&json!(-2.0)));
+        assert_eq!(hash(&json!(0)), hash(&json!(-0.0)));
+        assert_ne!(hash(&json!(1)), hash(&json!(1.5)));
+        assert_ne!(
+            hash(&json!({"a": 1, "b": 2})),
+            hash(&json!({"b": 2, "a": 1}))
+        );
+    }
+
+    #[test]
+    fn ord_agrees_with_eq() {
+        use crate::{JsonValue, Number};
+        use core::cmp::Ordering::*;
+
+        let numbers = [
+            Number::from(f64::NEG_INFINITY),
+            Number::from(i64::MIN),
+            Number::from(-2.5),
+            Number::from(-2i64),
+            Number::from(-1.5),
+            Number::from(0u64),
+            Number::from(0.5),
+            Number::from(9007199254740992.0),
+            Number::from(9007199254740993u64),
+            Number::from(u64::MAX),
+            Number::from(1e20),
+            Number::from(f64::INFINITY),
+            Number::from(f64::NAN),
+        ];
+        for (i, a) in numbers.iter().enumerate() {
+            for (j, b) in numbers.iter().enumerate() {
+                assert_eq!(a.cmp(b), i.cmp(&j), "{a} vs {b}");
+                assert_eq!(a == b, i == j, "{a} vs {b}");
+            }
+        }
+        assert_eq!(Number::from(-0.0).cmp(&Number::from(0.0)), Equal);
+        assert_eq!(Number::from(-0.0).cmp(&Number::from(0u64)), Equal);
+        assert_eq!(Number::from(-2.0).cmp(&Number::from(-2i64)), Equal);
+        assert_eq!(Number::from(-2.0), Number::from(-2i64));
+        assert_eq!(Number::from(f64::NAN), Number::from(-f64::NAN));
+        assert_ne!(Number::from(f64::NAN), Number::from(f64::INFINITY));
+
+        assert!(json!([1, 2]) < json!([1, 2, 0]));
+        assert!(json!([1, "a"]) < json!([1, []]));
+        assert!(json!({"a": 2}) < json!({"b": 1}));
+        assert!(json!({"a": 1, "b": 2}) < json!({"a": 1, "c": 0}));
+        assert_ne!(json!({"a": 1, "b": 2}).cmp(&json!({"b": 2, "a": 1})), Equal);
+        assert_eq!(json!({"a": [1.5]}).cmp(&json!({"a": [1.5]})), Equal);
+        assert!(JsonValue::Null < json!(true));
+        assert!(json!(true) < json!(-1e300));
+    }
+
+    #[test]
+    fn diff() {
+        use crate::Difference;
+
+        assert!(json!({"a": [1, {"b": 2}], "c": null})
+            .diff(&json!({"c": null, "a": [1, {"b": 2}]}))
+            .is_empty());
+
+        let diff = json!({"a/b": 1, "m~n": [1, 2], "x": true})
+            .diff(&json!({"a/b": "1", "m~n": [1], "y": false}));
+        assert_eq!(
+            diff,
+            vec![
+                Difference::Changed {
+                    path: "/a~1b".to_string(),
+                    before: json!(1),
+                    after: json!("1"),
+                },
+                Difference::Removed {
+                    path: "/m~0n/1".to_string(),
+                    value: json!(2),
+                },
+                Difference::Removed {
+                    path: "/x".to_string(),
+                    value: json!(true),
+                },
+                Difference::Added {
+                    path: "/y".to_string(),
+                    value: json!(false),
+                },
+            ]
+        );
+
+        let root = json!(1).diff(&json!([1]));
+        assert_eq!(root.len(), 1);
+        assert_eq!(root[0].path(), "");
+    }
+}
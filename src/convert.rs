@@ -0,0 +1,234 @@
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::fmt;
+
+use crate::{JsonValue, Map, Number};
+
+impl From<bool> for JsonValue {
+    fn from(b: bool) -> Self {
+        JsonValue::Bool(b)
+    }
+}
+
+impl From<&str> for JsonValue {
+    fn from(s: &str) -> Self {
+        JsonValue::String(s.to_string())
+    }
+}
+
+impl From<String> for JsonValue {
+    fn from(s: String) -> Self {
+        JsonValue::String(s)
+    }
+}
+
+impl From<Number> for JsonValue {
+    fn from(n: Number) -> Self {
+        JsonValue::Number(n)
+    }
+}
+
+impl From<Map> for JsonValue {
+    fn from(map: Map) -> Self {
+        JsonValue::Object(map)
+    }
+}
+
+/// Converts each element into a [`JsonValue`], giving a [`JsonValue::Array`].
+///
+/// ```
+/// use jsnom::JsonValue;
+///
+/// assert_eq!(JsonValue::from(vec![1, 2]).to_string(), "[1,2]");
+/// ```
+impl<T: Into<JsonValue>> From<Vec<T>> for JsonValue {
+    fn from(items: Vec<T>) -> Self {
+        JsonValue::Array(items.into_iter().map(Into::into).collect())
+    }
+}
+
+/// Converts `None` into [`JsonValue::Null`], and `Some` into its inner value.
+///
+/// ```
+/// use jsnom::JsonValue;
+///
+/// assert_eq!(JsonValue::from(None::<bool>), JsonValue::Null);
+/// assert_eq!(JsonValue::from(Some(true)), JsonValue::Bool(true));
+/// ```
+impl<T: Into<JsonValue>> From<Option<T>> for JsonValue {
+    fn from(value: Option<T>) -> Self {
+        value.map_or(JsonValue::Null, Into::into)
+    }
+}
+
+macro_rules! from_number {
+    ($($t:ty)*) => {
+        $(
+            impl From<$t> for JsonValue {
+                fn from(n: $t) -> Self {
+                    JsonValue::Number(Number::from(n))
+                }
+            }
+        )*
+    };
+}
+
+from_number!(u8 u16 u32 u64 usize i8 i16 i32 i64 isize f32 f64);
+
+/// The error returned when converting a [`JsonValue`] into a Rust type that it doesn't hold, such
+/// as a string into an `f64`.
+///
+/// ```
+/// use jsnom::JsonValue;
+///
+/// let err = f64::try_from(JsonValue::from("1.5")).unwrap_err();
+/// assert_eq!(err.to_string(), "expected a number, found a string");
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TypeError {
+    expected: &'static str,
+    found: &'static str,
+}
+
+impl TypeError {
+    fn new(expected: &'static str, value: &JsonValue) -> Self {
+        let found = match value {
+            JsonValue::Null => "null",
+            JsonValue::Bool(_) => "a boolean",
+            JsonValue::String(_) => "a string",
+            JsonValue::Number(_) => "a number",
+            JsonValue::Array(_) => "an array",
+            JsonValue::Object(_) => "an object",
+        };
+        Self { expected, found }
+    }
+
+    /// The kind of value that was wanted, such as `"a number"`.
+    pub fn expected(&self) -> &'static str {
+        self.expected
+    }
+
+    /// The kind of value that was found instead, such as `"a string"` or `"null"`.
+    pub fn found(&self) -> &'static str {
+        self.found
+    }
+}
+
+impl fmt::Display for TypeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "expected {}, found {}", self.expected, self.found)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for TypeError {}
+
+impl TryFrom<&JsonValue> for f64 {
+    type Error = TypeError;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        value.as_f64().ok_or(TypeError::new("a number", value))
+    }
+}
+
+impl TryFrom<JsonValue> for f64 {
+    type Error = TypeError;
+
+    fn try_from(value: JsonValue) -> Result<Self, Self::Error> {
+        f64::try_from(&value)
+    }
+}
+
+impl TryFrom<&JsonValue> for bool {
+    type Error = TypeError;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        value.as_bool().ok_or(TypeError::new("a boolean", value))
+    }
+}
+
+impl TryFrom<JsonValue> for bool {
+    type Error = TypeError;
+
+    fn try_from(value: JsonValue) -> Result<Self, Self::Error> {
+        bool::try_from(&value)
+    }
+}
+
+impl TryFrom<&JsonValue> for String {
+    type Error = TypeError;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        match value {
+            JsonValue::String(s) => Ok(s.clone()),
+            _ => Err(TypeError::new("a string", value)),
+        }
+    }
+}
+
+/// Takes the string out without copying it.
+///
+/// ```
+/// use jsnom::JsonValue;
+///
+/// let s: String = JsonValue::from("jsnom").try_into()?;
+/// assert_eq!(s, "jsnom");
+/// # Ok::<(), jsnom::TypeError>(())
+/// ```
+impl TryFrom<JsonValue> for String {
+    type Error = TypeError;
+
+    fn try_from(value: JsonValue) -> Result<Self, Self::Error> {
+        match value {
+            JsonValue::String(s) => Ok(s),
+            _ => Err(TypeError::new("a string", &value)),
+        }
+    }
+}
+
+impl TryFrom<&JsonValue> for Vec<JsonValue> {
+    type Error = TypeError;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        match value {
+            JsonValue::Array(items) => Ok(items.clone()),
+            _ => Err(TypeError::new("an array", value)),
+        }
+    }
+}
+
+impl TryFrom<JsonValue> for Vec<JsonValue> {
+    type Error = TypeError;
+
+    fn try_from(value: JsonValue) -> Result<Self, Self::Error> {
+        match value {
+            JsonValue::Array(items) => Ok(items),
+            _ => Err(TypeError::new("an array", &value)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::TypeError;
+    use crate::JsonValue;
+
+    #[test]
+    fn try_from() {
+        let value = json!({"n": 1.5, "b": true, "s": "x", "a": [null]});
+        assert_eq!(f64::try_from(&value["n"]), Ok(1.5));
+        assert_eq!(bool::try_from(value["b"].clone()), Ok(true));
+        assert_eq!(String::try_from(&value["s"]), Ok("x".to_string()));
+        assert_eq!(
+            Vec::<JsonValue>::try_from(value["a"].clone()),
+            Ok(vec![JsonValue::Null])
+        );
+
+        let err = Vec::<JsonValue>::try_from(&value).unwrap_err();
+        assert_eq!((err.expected(), err.found()), ("an array", "an object"));
+        let err: TypeError = bool::try_from(JsonValue::Null).unwrap_err();
+        assert_eq!(err.to_string(), "expected a boolean, found null");
+    }
+}
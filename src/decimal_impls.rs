@@ -0,0 +1,132 @@
+//! Conversions between [`Number`] and [`rust_decimal::Decimal`].
+
+use alloc::{format, string::ToString};
+
+use rust_decimal::Decimal;
+
+use crate::{parse, JsonValue, Number};
+
+impl Number {
+    /// Returns the number as a [`Decimal`], or `None` if it is out of `Decimal`'s range or has
+    /// more digits after the point than it can hold.
+    ///
+    /// A number parsed with
+    /// [`ParseOptions::preserve_number_literals`](crate::ParseOptions::preserve_number_literals)
+    /// set is converted from the text it was written as, so no digits are lost to rounding
+    /// through an `f64`. Otherwise a float is converted from its shortest decimal form.
+    ///
+    /// ```
+    /// use jsnom::{parse_with, ParseOptions};
+    /// use rust_decimal::Decimal;
+    ///
+    /// let opts = ParseOptions {
+    ///     preserve_number_literals: true,
+    ///     ..ParseOptions::default()
+    /// };
+    /// let value = parse_with("[0.10000000000000000001, 1.5e-3]", &opts).unwrap();
+    /// assert_eq!(
+    ///     value[0].as_decimal(),
+    ///     Some(Decimal::from_str_exact("0.10000000000000000001").unwrap())
+    /// );
+    /// assert_eq!(value[1].as_decimal(), Some(Decimal::new(15, 4)));
+    /// ```
+    pub fn as_decimal(&self) -> Option<Decimal> {
+        if let Some(literal) = self.literal() {
+            return decimal_from_str(literal);
+        }
+        if let Some(n) = self.as_u64() {
+            Some(Decimal::from(n))
+        } else if let Some(n) = self.as_i64() {
+            Some(Decimal::from(n))
+        } else {
+            let n = self.as_f64();
+            if !n.is_finite() {
+                return None;
+            }
+            Decimal::from_scientific(&format!("{n:e}")).ok()
+        }
+    }
+}
+
+fn decimal_from_str(s: &str) -> Option<Decimal> {
+    if s.contains(['e', 'E']) {
+        // `from_scientific` doesn't accept a `+` on the exponent, which JSON allows.
+        Decimal::from_scientific(&s.replacen("e+", "e", 1).replacen("E+", "e", 1)).ok()
+    } else {
+        Decimal::from_str_exact(s).ok()
+    }
+}
+
+impl JsonValue {
+    /// Returns the number as a [`Decimal`] if the value is a [`JsonValue::Number`] that fits in
+    /// one. See [`Number::as_decimal`].
+    pub fn as_decimal(&self) -> Option<Decimal> {
+        self.as_number()?.as_decimal()
+    }
+}
+
+/// Converts a [`Decimal`] exactly, keeping its digits as the number's literal so that they are
+/// written back out unchanged.
+///
+/// ```
+/// use jsnom::JsonValue;
+/// use rust_decimal::Decimal;
+///
+/// let price = JsonValue::from(Decimal::new(1050, 2));
+/// assert_eq!(price.to_string(), "10.50");
+/// assert_eq!(price.as_decimal(), Some(Decimal::new(1050, 2)));
+/// ```
+impl From<Decimal> for Number {
+    fn from(d: Decimal) -> Self {
+        let literal = d.to_string();
+        match parse::nom_number(&literal) {
+            Ok((_, JsonValue::Number(n))) => n.with_literal(&literal),
+            _ => unreachable!("`Decimal` is displayed as a valid JSON number"),
+        }
+    }
+}
+
+impl From<Decimal> for JsonValue {
+    fn from(d: Decimal) -> Self {
+        JsonValue::Number(Number::from(d))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use rust_decimal::Decimal;
+
+    use crate::{parse, parse_with, JsonValue, Number, ParseOptions};
+
+    #[test]
+    fn as_decimal() {
+        let opts = ParseOptions {
+            preserve_number_literals: true,
+            ..ParseOptions::default()
+        };
+        let value = parse_with("[12345678901234567890.123456789, -1E+2, 1e30]", &opts).unwrap();
+        assert_eq!(
+            value[0].as_decimal(),
+            Decimal::from_str_exact("12345678901234567890.123456789").ok()
+        );
+        assert_eq!(value[1].as_decimal(), Some(Decimal::from(-100)));
+        assert_eq!(value[2].as_decimal(), None);
+
+        // Without the literal, floats go through their shortest decimal form.
+        let value = parse("[0.1, -7, 18446744073709551615]").unwrap();
+        assert_eq!(value[0].as_decimal(), Some(Decimal::new(1, 1)));
+        assert_eq!(value[1].as_decimal(), Some(Decimal::from(-7)));
+        assert_eq!(value[2].as_decimal(), Some(Decimal::from(u64::MAX)));
+        assert_eq!(Number::from(f64::NAN).as_decimal(), None);
+        assert_eq!(JsonValue::Null.as_decimal(), None);
+    }
+
+    #[test]
+    fn from_decimal() {
+        let d = Decimal::from_str_exact("-0.000000000000000000000000001").unwrap();
+        let value = JsonValue::from(d);
+        assert_eq!(value.to_string(), "-0.000000000000000000000000001");
+        assert_eq!(value.as_decimal(), Some(d));
+        assert_eq!(JsonValue::from(Decimal::from(42)), JsonValue::from(42));
+    }
+}
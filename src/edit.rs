@@ -0,0 +1,294 @@
+use alloc::vec;
+
+use crate::{JsonValue, Map};
+
+/// How [`JsonValue::deep_merge_with`] combines two arrays at the same place.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ArrayMerge {
+    /// Append the elements of the second array to the first.
+    #[default]
+    Concat,
+    /// Use the second array in place of the first.
+    Replace,
+}
+
+impl JsonValue {
+    /// Apply an [RFC 7386](https://datatracker.ietf.org/doc/html/rfc7386) JSON Merge Patch.
+    ///
+    /// Objects in the patch are merged into the target recursively, with `null` values removing
+    /// the corresponding key. Any other patch value replaces the target outright.
+    ///
+    /// ```
+    /// use jsnom::json;
+    ///
+    /// let mut config = json!({"title": "Hello", "author": {"name": "A", "email": "a@b.c"}});
+    /// config.merge_patch(&json!({"title": "Goodbye", "author": {"email": null}}));
+    /// assert_eq!(config, json!({"title": "Goodbye", "author": {"name": "A"}}));
+    /// ```
+    pub fn merge_patch(&mut self, patch: &JsonValue) {
+        let JsonValue::Object(patch) = patch else {
+            *self = patch.clone();
+            return;
+        };
+        if !matches!(self, JsonValue::Object(_)) {
+            *self = JsonValue::Object(Map::new());
+        }
+        let JsonValue::Object(target) = self else {
+            unreachable!()
+        };
+
+        for (key, value) in patch {
+            if value.is_null() {
+                target.remove(key);
+            } else if let Some(existing) = target.get_mut(key) {
+                existing.merge_patch(value);
+            } else {
+                // Merging into `null` rather than cloning strips any nulls nested in the patch.
+                let mut new = JsonValue::Null;
+                new.merge_patch(value);
+                target.insert(key.clone(), new);
+            }
+        }
+    }
+
+    /// Merge `other` into this value, as when layering config files. Objects are merged
+    /// recursively, with entries only in `other` added at the end. Arrays are concatenated, and
+    /// anything else in `other`, `null` included, replaces what was here.
+    ///
+    /// Unlike [`JsonValue::merge_patch`], `null` doesn't remove keys, and arrays aren't replaced.
+    ///
+    /// ```
+    /// use jsnom::json;
+    ///
+    /// let mut config = json!({"name": "app", "plugins": ["a"], "db": {"host": "x", "port": 1}});
+    /// config.deep_merge(json!({"plugins": ["b"], "db": {"port": 2}, "debug": true}));
+    /// assert_eq!(
+    ///     config,
+    ///     json!({"name": "app", "plugins": ["a", "b"], "db": {"host": "x", "port": 2}, "debug": true})
+    /// );
+    /// ```
+    pub fn deep_merge(&mut self, other: JsonValue) {
+        self.deep_merge_with(other, ArrayMerge::Concat);
+    }
+
+    /// Like [`JsonValue::deep_merge`], but combining arrays as `arrays` says.
+    ///
+    /// ```
+    /// use jsnom::{json, ArrayMerge};
+    ///
+    /// let mut config = json!({"plugins": ["a"]});
+    /// config.deep_merge_with(json!({"plugins": ["b"]}), ArrayMerge::Replace);
+    /// assert_eq!(config, json!({"plugins": ["b"]}));
+    /// ```
+    pub fn deep_merge_with(&mut self, other: JsonValue, arrays: ArrayMerge) {
+        match (self, other) {
+            (JsonValue::Object(target), JsonValue::Object(other)) => {
+                for (key, value) in other {
+                    match target.get_mut(&key) {
+                        Some(existing) => existing.deep_merge_with(value, arrays),
+                        None => {
+                            target.insert(key, value);
+                        }
+                    }
+                }
+            }
+            (JsonValue::Array(target), JsonValue::Array(other)) if arrays == ArrayMerge::Concat => {
+                target.extend(other);
+            }
+            (target, other) => *target = other,
+        }
+    }
+
+    /// Removes every object entry whose value is `null`, however deeply nested. `null` elements of
+    /// arrays are kept, so that the positions of the other elements don't change; see
+    /// [`JsonValue::remove_all_nulls`] to remove those too.
+    ///
+    /// ```
+    /// use jsnom::json;
+    ///
+    /// let mut user = json!({"name": "A", "email": null, "pets": [{"name": null}, null]});
+    /// user.remove_nulls();
+    /// assert_eq!(user, json!({"name": "A", "pets": [{}, null]}));
+    /// ```
+    pub fn remove_nulls(&mut self) {
+        self.strip_nulls(false);
+    }
+
+    /// Like [`JsonValue::remove_nulls`], but also removes `null` elements from arrays.
+    ///
+    /// ```
+    /// use jsnom::json;
+    ///
+    /// let mut user = json!({"name": "A", "email": null, "pets": [{"name": null}, null]});
+    /// user.remove_all_nulls();
+    /// assert_eq!(user, json!({"name": "A", "pets": [{}]}));
+    /// ```
+    pub fn remove_all_nulls(&mut self) {
+        self.strip_nulls(true);
+    }
+
+    fn strip_nulls(&mut self, from_arrays: bool) {
+        let mut stack = vec![self];
+        while let Some(value) = stack.pop() {
+            match value {
+                JsonValue::Array(items) => {
+                    if from_arrays {
+                        items.retain(|item| !item.is_null());
+                    }
+                    stack.extend(items);
+                }
+                JsonValue::Object(map) => {
+                    map.retain(|_, value| !value.is_null());
+                    stack.extend(map.values_mut());
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Sorts the entries of every object by key, however deeply nested, giving the same output
+    /// for objects that only differ in the order of their keys.
+    ///
+    /// ```
+    /// use jsnom::json;
+    ///
+    /// let mut value = json!({"b": [{"d": 1, "c": 2}], "a": null});
+    /// value.sort_keys();
+    /// assert_eq!(value.to_string(), r#"{"a":null,"b":[{"c":2,"d":1}]}"#);
+    /// ```
+    pub fn sort_keys(&mut self) {
+        let mut stack = vec![self];
+        while let Some(value) = stack.pop() {
+            match value {
+                JsonValue::Array(items) => stack.extend(items),
+                JsonValue::Object(map) => {
+                    map.sort_keys();
+                    stack.extend(map.values_mut());
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::JsonValue;
+
+    fn merge_patch(mut target: JsonValue, patch: JsonValue) -> JsonValue {
+        target.merge_patch(&patch);
+        target
+    }
+
+    // The examples from appendix A of RFC 7386.
+    #[test]
+    fn rfc_examples() {
+        assert_eq!(
+            merge_patch(json!({"a": "b"}), json!({"a": "c"})),
+            json!({"a": "c"})
+        );
+        assert_eq!(
+            merge_patch(json!({"a": "b"}), json!({"b": "c"})),
+            json!({"a": "b", "b": "c"})
+        );
+        assert_eq!(
+            merge_patch(json!({"a": "b"}), json!({"a": null})),
+            json!({})
+        );
+        assert_eq!(
+            merge_patch(json!({"a": "b", "b": "c"}), json!({"a": null})),
+            json!({"b": "c"})
+        );
+        assert_eq!(
+            merge_patch(json!({"a": ["b"]}), json!({"a": "c"})),
+            json!({"a": "c"})
+        );
+        assert_eq!(
+            merge_patch(json!({"a": "c"}), json!({"a": ["b"]})),
+            json!({"a": ["b"]})
+        );
+        assert_eq!(
+            merge_patch(
+                json!({"a": {"b": "c"}}),
+                json!({"a": {"b": "d", "c": null}})
+            ),
+            json!({"a": {"b": "d"}})
+        );
+        assert_eq!(
+            merge_patch(json!({"a": [{"b": "c"}]}), json!({"a": [1]})),
+            json!({"a": [1]})
+        );
+        assert_eq!(
+            merge_patch(json!(["a", "b"]), json!(["c", "d"])),
+            json!(["c", "d"])
+        );
+        assert_eq!(merge_patch(json!({"a": "b"}), json!(["c"])), json!(["c"]));
+        assert_eq!(merge_patch(json!({"a": "foo"}), json!(null)), json!(null));
+        assert_eq!(merge_patch(json!({"a": "foo"}), json!("bar")), json!("bar"));
+        assert_eq!(
+            merge_patch(json!({"e": null}), json!({"a": 1})),
+            json!({"e": null, "a": 1})
+        );
+        assert_eq!(
+            merge_patch(json!([1, 2]), json!({"a": "b", "c": null})),
+            json!({"a": "b"})
+        );
+        assert_eq!(
+            merge_patch(json!({}), json!({"a": {"bb": {"ccc": null}}})),
+            json!({"a": {"bb": {}}})
+        );
+    }
+
+    #[test]
+    fn deep_merge() {
+        use crate::ArrayMerge;
+
+        let base = json!({"a": {"b": [1], "c": {"d": 1}}, "e": [1], "f": 1});
+        let layer = json!({"a": {"b": [2], "c": null}, "e": {"x": 1}, "f": [1], "g": {}});
+
+        let mut merged = base.clone();
+        merged.deep_merge(layer.clone());
+        assert_eq!(
+            merged,
+            json!({"a": {"b": [1, 2], "c": null}, "e": {"x": 1}, "f": [1], "g": {}})
+        );
+
+        let mut merged = base;
+        merged.deep_merge_with(layer, ArrayMerge::Replace);
+        assert_eq!(
+            merged,
+            json!({"a": {"b": [2], "c": null}, "e": {"x": 1}, "f": [1], "g": {}})
+        );
+    }
+
+    #[test]
+    fn remove_nulls() {
+        let value = json!([null, {"a": null, "b": [null, {"c": null, "d": 0}]}, [[null]]]);
+
+        let mut stripped = value.clone();
+        stripped.remove_nulls();
+        assert_eq!(stripped, json!([null, {"b": [null, {"d": 0}]}, [[null]]]));
+
+        let mut stripped = value;
+        stripped.remove_all_nulls();
+        assert_eq!(stripped, json!([{"b": [{"d": 0}]}, [[]]]));
+
+        let mut null = JsonValue::Null;
+        null.remove_all_nulls();
+        assert_eq!(null, JsonValue::Null);
+    }
+
+    #[test]
+    fn sort_keys() {
+        let mut value = json!([
+            {"z": {"y": 1, "x": [{"b": 2, "a": 1}]}, "m": null},
+            [{"q": true, "p": false}],
+            "unchanged"
+        ]);
+        value.sort_keys();
+        assert_eq!(
+            value.to_string(),
+            r#"[{"m":null,"z":{"x":[{"a":1,"b":2}],"y":1}},[{"p":false,"q":true}],"unchanged"]"#
+        );
+    }
+}
@@ -0,0 +1,449 @@
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::{fmt, str::Utf8Error};
+
+use nom::error::{convert_error, VerboseError, VerboseErrorKind};
+
+/// The error type returned from parsers. It is essentially a wrapper around
+/// [`nom::error::VerboseError`] using a different [`std::fmt::Display`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct Error<'a> {
+    pub errors: Vec<(&'a str, VerboseErrorKind)>,
+    data: &'a str,
+    raw_error: VerboseError<&'a str>,
+}
+
+#[cfg(feature = "std")]
+impl<'a> std::error::Error for Error<'a> {}
+
+impl<'a> fmt::Display for Error<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        if self.is_empty_input() {
+            return f.write_str("input is empty");
+        }
+        write!(f, "{}", convert_error(self.data, self.raw_error.clone()))
+    }
+}
+
+impl<'a> Error<'a> {
+    pub(crate) fn from_raw(data: &'a str, raw: VerboseError<&'a str>) -> Self {
+        Self {
+            errors: raw.clone().errors,
+            data,
+            raw_error: raw,
+        }
+    }
+
+    /// Convert into an [`OwnedError`], which doesn't borrow the input and so can be returned from
+    /// functions that own it, or sent between threads.
+    ///
+    /// ```
+    /// use jsnom::JsonValue;
+    ///
+    /// fn load(path: &str) -> Result<JsonValue, Box<dyn std::error::Error + Send + Sync>> {
+    ///     let contents = format!("[\"{path}\", nul]");
+    ///     Ok(jsnom::parse(&contents).map_err(|e| e.into_owned())?)
+    /// }
+    ///
+    /// assert!(load("config.json").is_err());
+    /// ```
+    pub fn into_owned(self) -> OwnedError {
+        OwnedError::from(self)
+    }
+
+    /// What went wrong, judged from the innermost failure.
+    ///
+    /// ```
+    /// use jsnom::{parse, ErrorKind};
+    ///
+    /// assert_eq!(parse("[1, 2").unwrap_err().kind(), ErrorKind::UnexpectedEof);
+    /// assert_eq!(parse("[1, ?]").unwrap_err().kind(), ErrorKind::UnexpectedChar('?'));
+    /// assert_eq!(parse("\"\\x\"").unwrap_err().kind(), ErrorKind::InvalidEscape);
+    /// assert_eq!(parse("  \n").unwrap_err().kind(), ErrorKind::EmptyInput);
+    /// ```
+    pub fn kind(&self) -> ErrorKind {
+        if self.is_empty_input() {
+            return ErrorKind::EmptyInput;
+        }
+        ErrorKind::classify(self.data, &self.errors)
+    }
+
+    // Whether there was nothing but whitespace to parse, and perhaps a byte order mark.
+    fn is_empty_input(&self) -> bool {
+        let data = self.data.strip_prefix('\u{feff}').unwrap_or(self.data);
+        data.trim_matches([' ', '\t', '\n', '\r']).is_empty()
+    }
+
+    /// Whether the input could still become valid if more were added to the end, as when a
+    /// stream has been cut off part way through a value. This is the same as checking for
+    /// [`ErrorKind::UnexpectedEof`]. Input that can never be valid, however much follows, gives
+    /// `false`, as does input with nothing but whitespace, which is [`ErrorKind::EmptyInput`].
+    ///
+    /// ```
+    /// use jsnom::parse;
+    ///
+    /// assert!(parse("{\"a\":").unwrap_err().is_incomplete());
+    /// assert!(parse("[1, nul").unwrap_err().is_incomplete());
+    /// assert!(parse("[1.").unwrap_err().is_incomplete());
+    /// assert!(!parse("{\"a\": ?").unwrap_err().is_incomplete());
+    /// ```
+    pub fn is_incomplete(&self) -> bool {
+        self.kind() == ErrorKind::UnexpectedEof
+    }
+
+    /// A short, single-line description of what went wrong and where, for logging. The
+    /// [`Display`](fmt::Display) output is longer and annotates the offending line instead.
+    ///
+    /// ```
+    /// use jsnom::parse;
+    ///
+    /// let error = parse("{\"a\": [\n  1,\n  2 3]}").unwrap_err();
+    /// assert_eq!(error.message(), "expected ']', found '3' at line 3 column 5");
+    /// ```
+    pub fn message(&self) -> String {
+        let kind = self.kind();
+        // The innermost thing that was expected, which is any value if the parsers were choosing
+        // between the kinds of value there.
+        let alt = VerboseErrorKind::Nom(nom::error::ErrorKind::Alt);
+        let expected = match self.errors.first() {
+            _ if self.errors.iter().any(|(_, kind)| *kind == alt) => Some(String::from("a value")),
+            Some((_, VerboseErrorKind::Char(c))) => Some(format!("{c:?}")),
+            _ => None,
+        };
+        let summary = match (kind, expected) {
+            (ErrorKind::EmptyInput, _) => return String::from("input is empty"),
+            (ErrorKind::UnexpectedChar(c), Some(expected)) => {
+                format!("expected {expected}, found {c:?}")
+            }
+            (ErrorKind::UnexpectedEof, Some(expected)) => {
+                format!("expected {expected}, found the end of input")
+            }
+            (kind, _) => kind.to_string(),
+        };
+        match self.location() {
+            Some((line, column)) => format!("{summary} at line {line} column {column}"),
+            None => summary,
+        }
+    }
+
+    /// The 1-based line and column, counted in characters, at which parsing failed.
+    ///
+    /// ```
+    /// use jsnom::parse;
+    ///
+    /// let error = parse("[\n  true,\n  nul\n]").unwrap_err();
+    /// assert_eq!(error.location(), Some((3, 3)));
+    /// ```
+    pub fn location(&self) -> Option<(usize, usize)> {
+        // The innermost failure comes first, and its input is always a suffix of `data`.
+        let (rest, _) = self.errors.first()?;
+        let consumed = &self.data[..self.data.len() - rest.len()];
+        let line_start = consumed.rfind('\n').map_or(0, |i| i + 1);
+        Some((
+            consumed.matches('\n').count() + 1,
+            consumed[line_start..].chars().count() + 1,
+        ))
+    }
+}
+
+/// The broad category of a parse failure, as given by [`Error::kind`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ErrorKind {
+    /// There was nothing to parse but whitespace, as when an empty body was received.
+    EmptyInput,
+    /// The input ended before the value was complete, as when a stream has been cut off, so more
+    /// input could still make it valid. This includes ending part way through a string, comment,
+    /// number, escape or literal such as `tru`. See [`Error::is_incomplete`].
+    UnexpectedEof,
+    /// A character appeared where it isn't allowed.
+    UnexpectedChar(char),
+    /// A string contains a backslash that doesn't start a valid escape.
+    InvalidEscape,
+    /// A number is malformed, such as `1.` or `1e`.
+    InvalidNumber,
+    /// A number is too large in magnitude to be represented.
+    NumberOutOfRange,
+    /// A key is repeated within an object, with
+    /// [`ParseOptions::reject_duplicate_keys`](crate::ParseOptions::reject_duplicate_keys) set.
+    DuplicateKey,
+    /// Arrays and objects are nested deeper than
+    /// [`ParseOptions::max_depth`](crate::ParseOptions::max_depth).
+    TooDeep,
+    /// The input is longer than
+    /// [`ParseOptions::max_length`](crate::ParseOptions::max_length).
+    TooLong,
+    /// The input holds more values than
+    /// [`ParseOptions::max_nodes`](crate::ParseOptions::max_nodes).
+    TooManyValues,
+    /// Something other than whitespace follows the value, for parsers that don't allow it.
+    TrailingData,
+    /// The input is not valid UTF-8.
+    InvalidUtf8,
+}
+
+// A short description, used by `Error::message`.
+impl fmt::Display for ErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match self {
+            ErrorKind::EmptyInput => f.write_str("input is empty"),
+            ErrorKind::UnexpectedEof => f.write_str("unexpected end of input"),
+            ErrorKind::UnexpectedChar(c) => write!(f, "unexpected character {c:?}"),
+            ErrorKind::InvalidEscape => f.write_str("invalid escape"),
+            ErrorKind::InvalidNumber => f.write_str("invalid number"),
+            ErrorKind::NumberOutOfRange => f.write_str("number out of range"),
+            ErrorKind::DuplicateKey => f.write_str("duplicate object key"),
+            ErrorKind::TooDeep => f.write_str("maximum nesting depth exceeded"),
+            ErrorKind::TooLong => f.write_str("maximum input length exceeded"),
+            ErrorKind::TooManyValues => f.write_str("maximum number of values exceeded"),
+            ErrorKind::TrailingData => f.write_str("unexpected data after the value"),
+            ErrorKind::InvalidUtf8 => f.write_str("input is not valid UTF-8"),
+        }
+    }
+}
+
+impl ErrorKind {
+    // Works out the kind from the innermost failure and the innermost label the parsers gave it.
+    // Limits are reported as such even at the end of the input, since more won't help.
+    fn classify(data: &str, errors: &[(&str, VerboseErrorKind)]) -> Self {
+        let Some((rest, kind)) = errors.first() else {
+            return ErrorKind::UnexpectedEof;
+        };
+        if *kind == VerboseErrorKind::Nom(nom::error::ErrorKind::Eof) && !rest.is_empty() {
+            return ErrorKind::TrailingData;
+        }
+        // Labels from inside an `alt` only say which kinds of value didn't match, so are skipped.
+        let alt = VerboseErrorKind::Nom(nom::error::ErrorKind::Alt);
+        let outside_alt = errors
+            .iter()
+            .rposition(|(_, kind)| *kind == alt)
+            .unwrap_or(0);
+        let label = errors[outside_alt..]
+            .iter()
+            .find_map(|(_, kind)| match kind {
+                VerboseErrorKind::Context(label) => Some(*label),
+                _ => None,
+            });
+        match label {
+            Some("maximum nesting depth exceeded") => return ErrorKind::TooDeep,
+            Some("maximum input length exceeded") => return ErrorKind::TooLong,
+            Some("maximum number of values exceeded") => return ErrorKind::TooManyValues,
+            Some("duplicate object key") => return ErrorKind::DuplicateKey,
+            Some("number out of range") => return ErrorKind::NumberOutOfRange,
+            // A block comment that hasn't been closed by the end of the input.
+            Some("comment") => return ErrorKind::UnexpectedEof,
+            _ if is_truncated(data, rest) => return ErrorKind::UnexpectedEof,
+            Some("escape") => return ErrorKind::InvalidEscape,
+            Some("number") => return ErrorKind::InvalidNumber,
+            _ => {}
+        }
+        match rest.chars().next() {
+            Some(c) => ErrorKind::UnexpectedChar(c),
+            None => ErrorKind::UnexpectedEof,
+        }
+    }
+}
+
+// Whether parsing failed at `rest` only for want of more input: at the very end, or part way
+// through a `-`, a literal or a `\u` escape.
+fn is_truncated(data: &str, rest: &str) -> bool {
+    let consumed = &data[..data.len() - rest.len()];
+    rest.is_empty()
+        || rest == "-"
+        || ["true", "false", "null"]
+            .iter()
+            .any(|literal| literal.len() > rest.len() && literal.starts_with(rest))
+        || (consumed.ends_with("\\u")
+            && rest.len() < 4
+            && rest.bytes().all(|b| b.is_ascii_hexdigit()))
+}
+
+/// An [`Error`] that owns its data rather than borrowing the input, so that it can outlive it.
+#[derive(Clone, Debug, PartialEq)]
+pub struct OwnedError {
+    /// The remaining input at each point of failure, along with what went wrong there.
+    pub errors: Vec<(String, VerboseErrorKind)>,
+    display: String,
+    message: String,
+    kind: ErrorKind,
+    location: Option<(usize, usize)>,
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for OwnedError {}
+
+impl OwnedError {
+    // Input that isn't UTF-8 never reaches the parsers, so there are no `errors` to report.
+    pub(crate) fn invalid_utf8(error: Utf8Error) -> Self {
+        Self {
+            errors: Vec::new(),
+            display: format!("input is not valid UTF-8: {error}"),
+            message: format!("input is not valid UTF-8: {error}"),
+            kind: ErrorKind::InvalidUtf8,
+            location: None,
+        }
+    }
+
+    /// What went wrong, as for [`Error::kind`].
+    pub fn kind(&self) -> ErrorKind {
+        self.kind
+    }
+
+    /// The 1-based line and column at which parsing failed, as for [`Error::location`].
+    pub fn location(&self) -> Option<(usize, usize)> {
+        self.location
+    }
+
+    /// A short, single-line description of what went wrong, as for [`Error::message`].
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// Whether more input could still make it valid, as for [`Error::is_incomplete`].
+    pub fn is_incomplete(&self) -> bool {
+        self.kind == ErrorKind::UnexpectedEof
+    }
+}
+
+impl fmt::Display for OwnedError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        f.write_str(&self.display)
+    }
+}
+
+impl From<Error<'_>> for OwnedError {
+    fn from(error: Error<'_>) -> Self {
+        Self {
+            display: error.to_string(),
+            message: error.message(),
+            kind: error.kind(),
+            location: error.location(),
+            errors: error
+                .errors
+                .into_iter()
+                .map(|(s, kind)| (s.to_string(), kind))
+                .collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        parse, parse_bytes, parse_strict, parse_with, ErrorKind, OwnedError, ParseOptions,
+    };
+
+    #[test]
+    fn location_counts_chars() {
+        let error = parse("{\"é\": tru}").unwrap_err();
+        assert_eq!(error.location(), Some((1, 7)));
+        assert_eq!(OwnedError::from(error).location(), Some((1, 7)));
+    }
+
+    #[test]
+    fn kinds() {
+        let kind = |s| parse(s).unwrap_err().kind();
+        assert_eq!(kind(""), ErrorKind::EmptyInput);
+        assert_eq!(kind("\u{feff} \r\n\t"), ErrorKind::EmptyInput);
+        assert_eq!(parse("").unwrap_err().to_string(), "input is empty");
+        assert_eq!(kind("{\"a\": [1, 2"), ErrorKind::UnexpectedEof);
+        assert_eq!(kind("[1 2]"), ErrorKind::UnexpectedChar('2'));
+        assert_eq!(kind("\"abc"), ErrorKind::UnexpectedEof);
+        assert_eq!(kind("[1."), ErrorKind::UnexpectedEof);
+        assert_eq!(kind("[1e"), ErrorKind::UnexpectedEof);
+        assert_eq!(kind("[1e+"), ErrorKind::UnexpectedEof);
+        assert_eq!(kind("[tr"), ErrorKind::UnexpectedEof);
+        assert_eq!(kind("-"), ErrorKind::UnexpectedEof);
+        assert_eq!(kind("\"ab\\"), ErrorKind::UnexpectedEof);
+        assert_eq!(kind("[\"\\u12"), ErrorKind::UnexpectedEof);
+        assert_eq!(kind("{\"a\": 1,"), ErrorKind::UnexpectedEof);
+        assert_eq!(kind("[\"\\u12\"]"), ErrorKind::InvalidEscape);
+        assert_eq!(kind("[1.]"), ErrorKind::InvalidNumber);
+        assert_eq!(kind("1e400"), ErrorKind::NumberOutOfRange);
+        assert_eq!(kind(&"[".repeat(200)), ErrorKind::TooDeep);
+        assert_eq!(
+            parse_strict("true false").unwrap_err().kind(),
+            ErrorKind::TrailingData
+        );
+
+        let opts = ParseOptions {
+            reject_duplicate_keys: true,
+            allow_comments: true,
+            ..ParseOptions::default()
+        };
+        let kind = |s| parse_with(s, &opts).unwrap_err().kind();
+        assert_eq!(kind("{\"a\": 1, \"a\": 2}"), ErrorKind::DuplicateKey);
+        assert_eq!(kind("[1 /* 2 ]"), ErrorKind::UnexpectedEof);
+
+        let opts = ParseOptions {
+            max_length: Some(8),
+            max_nodes: Some(3),
+            ..ParseOptions::default()
+        };
+        let kind = |s| parse_with(s, &opts).unwrap_err().kind();
+        assert_eq!(kind("[1, 2, 3]"), ErrorKind::TooLong);
+        assert_eq!(kind("[1,2,3]"), ErrorKind::TooManyValues);
+        assert!(parse_with("[1,2]", &opts).is_ok());
+
+        assert_eq!(
+            parse_bytes(b"\xff").unwrap_err().kind(),
+            ErrorKind::InvalidUtf8
+        );
+        assert_eq!(
+            OwnedError::from(parse("[").unwrap_err()).kind(),
+            ErrorKind::UnexpectedEof
+        );
+    }
+
+    #[test]
+    fn message() {
+        let message = |s| parse(s).unwrap_err().message();
+        assert_eq!(message(""), "input is empty");
+        assert_eq!(
+            message("[1, 2"),
+            "expected ']', found the end of input at line 1 column 6"
+        );
+        assert_eq!(
+            message("[1, ?]"),
+            "expected a value, found '?' at line 1 column 5"
+        );
+        assert_eq!(
+            message("{\"a\" 1}"),
+            "expected ':', found '1' at line 1 column 6"
+        );
+        assert_eq!(
+            message("\"abc"),
+            "expected '\"', found the end of input at line 1 column 5"
+        );
+        assert_eq!(message("[\n\n  1.]"), "invalid number at line 3 column 5");
+        assert_eq!(
+            parse_strict("true false").unwrap_err().message(),
+            "unexpected data after the value at line 1 column 6"
+        );
+        assert_eq!(
+            parse_bytes(b"\xff").unwrap_err().message(),
+            "input is not valid UTF-8: invalid utf-8 sequence of 1 bytes from index 0"
+        );
+        let error = parse("[1 2]").unwrap_err();
+        assert!(!error.message().contains('\n'));
+        assert_eq!(OwnedError::from(error.clone()).message(), error.message());
+    }
+
+    #[test]
+    fn incomplete() {
+        let complete = "{\"a\": [1.5e+3, -2, true, false, null, \"\\u00e9\\uD83D\\uDE00\\\\\"]}";
+        assert!(parse(complete).is_ok());
+        assert!(!parse("").unwrap_err().is_incomplete());
+        for end in 1..complete.len() {
+            let error = parse(&complete[..end]).unwrap_err();
+            assert!(error.is_incomplete(), "{:?}", &complete[..end]);
+            assert!(OwnedError::from(error).is_incomplete());
+        }
+        for s in ["[1 2", "[tru ]", "\"\\x", "{\"a\" 1", "[-a", "1e400"] {
+            assert!(!parse(s).unwrap_err().is_incomplete(), "{s:?}");
+        }
+    }
+}
@@ -0,0 +1,319 @@
+use alloc::{
+    collections::BTreeMap,
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::fmt::{self, Write};
+
+use crate::{JsonValue, Map, PathSegment};
+
+/// The error returned from [`JsonValue::from_flat`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum FromFlatError {
+    /// The path is malformed, such as `a[x]` or `a[0`.
+    InvalidPath(String),
+    /// The path was already given a value, or passes through a value that isn't the array or
+    /// object it needs, as in `a = 1` followed by `a.b = 2`.
+    Conflict(String),
+}
+
+impl fmt::Display for FromFlatError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::InvalidPath(path) => write!(f, "invalid path `{path}`"),
+            Self::Conflict(path) => write!(f, "conflicting values at path `{path}`"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for FromFlatError {}
+
+impl JsonValue {
+    /// Flattens this value into a list of paths to its leaves along with their values, in order.
+    /// Object keys are joined with `.`, and array indices are written in brackets, so
+    /// `{"a": {"b": [1]}}` becomes `[("a.b[0]", 1)]`.
+    ///
+    /// The leaves are the scalars along with any empty arrays and objects, so that nothing is
+    /// lost. A scalar on its own flattens to a single entry with an empty path. Keys holding `.`
+    /// or `[` are written as they are, making the path ambiguous.
+    ///
+    /// ```
+    /// use jsnom::{json, JsonValue};
+    ///
+    /// let config = json!({"db": {"hosts": ["a", "b"], "port": 5432}, "extra": {}});
+    /// assert_eq!(
+    ///     config.flatten(),
+    ///     [
+    ///         ("db.hosts[0]".to_string(), JsonValue::from("a")),
+    ///         ("db.hosts[1]".to_string(), JsonValue::from("b")),
+    ///         ("db.port".to_string(), JsonValue::from(5432)),
+    ///         ("extra".to_string(), json!({})),
+    ///     ]
+    /// );
+    /// ```
+    pub fn flatten(&self) -> Vec<(String, JsonValue)> {
+        self.flatten_with(".")
+    }
+
+    /// Like [`JsonValue::flatten`], but joins object keys with `separator` instead of `.`.
+    ///
+    /// ```
+    /// use jsnom::{json, JsonValue};
+    ///
+    /// let config = json!({"db": {"port": 5432}});
+    /// assert_eq!(
+    ///     config.flatten_with("__"),
+    ///     [("db__port".to_string(), JsonValue::from(5432))]
+    /// );
+    /// ```
+    pub fn flatten_with(&self, separator: &str) -> Vec<(String, JsonValue)> {
+        self.descendants_with_path()
+            .filter(|(_, value)| match value {
+                JsonValue::Array(items) => items.is_empty(),
+                JsonValue::Object(map) => map.is_empty(),
+                _ => true,
+            })
+            .map(|(path, value)| (join_path(&path, separator), value.clone()))
+            .collect()
+    }
+}
+
+impl JsonValue {
+    /// Rebuilds a value from paths and the values at them, as given by [`JsonValue::flatten`].
+    /// Objects and arrays are created as the paths need them, with objects keeping their keys in
+    /// the order they were first seen, and any array elements that no path reaches are `null`.
+    ///
+    /// Fails if a path is malformed, or if two paths disagree about what is at some point, such
+    /// as `a` being both a number and an object.
+    ///
+    /// ```
+    /// use jsnom::{json, JsonValue};
+    ///
+    /// let pairs = [
+    ///     ("db.hosts[0]".to_string(), JsonValue::from("a")),
+    ///     ("db.hosts[1]".to_string(), JsonValue::from("b")),
+    ///     ("db.port".to_string(), JsonValue::from(5432)),
+    /// ];
+    /// assert_eq!(
+    ///     JsonValue::from_flat(pairs),
+    ///     Ok(json!({"db": {"hosts": ["a", "b"], "port": 5432}}))
+    /// );
+    ///
+    /// let conflict = [("a".to_string(), json!(1)), ("a.b".to_string(), json!(2))];
+    /// assert!(JsonValue::from_flat(conflict).is_err());
+    /// ```
+    pub fn from_flat(
+        pairs: impl IntoIterator<Item = (String, JsonValue)>,
+    ) -> Result<JsonValue, FromFlatError> {
+        JsonValue::from_flat_with(pairs, ".")
+    }
+
+    /// Like [`JsonValue::from_flat`], but for paths whose object keys are joined with
+    /// `separator`, as given by [`JsonValue::flatten_with`].
+    pub fn from_flat_with(
+        pairs: impl IntoIterator<Item = (String, JsonValue)>,
+        separator: &str,
+    ) -> Result<JsonValue, FromFlatError> {
+        let mut root = Node::Unset;
+        for (path, value) in pairs {
+            let Some(segments) = split_path(&path, separator) else {
+                return Err(FromFlatError::InvalidPath(path));
+            };
+            let mut node = &mut root;
+            for segment in segments {
+                match node.child(segment) {
+                    Some(child) => node = child,
+                    None => return Err(FromFlatError::Conflict(path)),
+                }
+            }
+            if !node.set(value) {
+                return Err(FromFlatError::Conflict(path));
+            }
+        }
+        Ok(root.into_value())
+    }
+}
+
+fn join_path(path: &[PathSegment], separator: &str) -> String {
+    let mut joined = String::new();
+    for segment in path {
+        match segment {
+            PathSegment::Key(key) => {
+                if !joined.is_empty() {
+                    joined.push_str(separator);
+                }
+                joined.push_str(key);
+            }
+            PathSegment::Index(i) => {
+                let _ = write!(joined, "[{i}]");
+            }
+        }
+    }
+    joined
+}
+
+// A value being rebuilt by `JsonValue::from_flat`, which tells the arrays and objects made along
+// the way apart from values that were given, and knows which array elements are still missing.
+enum Node {
+    Unset,
+    Leaf(JsonValue),
+    Array(Vec<Node>),
+    Object(Vec<(String, Node)>, BTreeMap<String, usize>),
+}
+
+impl Node {
+    fn child(&mut self, segment: PathSegment) -> Option<&mut Node> {
+        if let Node::Unset = self {
+            *self = match segment {
+                PathSegment::Key(_) => Node::Object(Vec::new(), BTreeMap::new()),
+                PathSegment::Index(_) => Node::Array(Vec::new()),
+            };
+        }
+        match (self, segment) {
+            (Node::Array(items), PathSegment::Index(i)) => {
+                if i >= items.len() {
+                    items.resize_with(i + 1, || Node::Unset);
+                }
+                Some(&mut items[i])
+            }
+            (Node::Object(entries, index), PathSegment::Key(key)) => {
+                let i = *index.entry(key.clone()).or_insert_with(|| {
+                    entries.push((key, Node::Unset));
+                    entries.len() - 1
+                });
+                Some(&mut entries[i].1)
+            }
+            _ => None,
+        }
+    }
+
+    // Empty arrays and objects are kept as containers, so that other paths can still add to them.
+    fn set(&mut self, value: JsonValue) -> bool {
+        match (&*self, value) {
+            (Node::Unset, JsonValue::Array(items)) if items.is_empty() => {
+                *self = Node::Array(Vec::new());
+            }
+            (Node::Unset, JsonValue::Object(map)) if map.is_empty() => {
+                *self = Node::Object(Vec::new(), BTreeMap::new());
+            }
+            (Node::Unset, value) => *self = Node::Leaf(value),
+            (Node::Array(_), JsonValue::Array(items)) if items.is_empty() => {}
+            (Node::Object(..), JsonValue::Object(map)) if map.is_empty() => {}
+            _ => return false,
+        }
+        true
+    }
+
+    fn into_value(self) -> JsonValue {
+        match self {
+            Node::Unset => JsonValue::Null,
+            Node::Leaf(value) => value,
+            Node::Array(items) => {
+                JsonValue::Array(items.into_iter().map(Node::into_value).collect())
+            }
+            Node::Object(entries, _) => JsonValue::Object(Map::from(
+                entries
+                    .into_iter()
+                    .map(|(key, node)| (key, node.into_value()))
+                    .collect::<Vec<_>>(),
+            )),
+        }
+    }
+}
+
+// Splits a path written by `join_path` back into its segments.
+fn split_path(path: &str, separator: &str) -> Option<Vec<PathSegment>> {
+    let mut segments = Vec::new();
+    let mut rest = path;
+    if rest.is_empty() {
+        return Some(segments);
+    }
+    let mut expect_key = !rest.starts_with('[');
+    loop {
+        if let Some(after) = rest.strip_prefix('[') {
+            let (digits, after) = after.split_once(']')?;
+            if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+                return None;
+            }
+            segments.push(PathSegment::Index(digits.parse().ok()?));
+            rest = after;
+        } else if expect_key {
+            let end = [rest.find(separator), rest.find('[')]
+                .into_iter()
+                .flatten()
+                .min()
+                .unwrap_or(rest.len());
+            segments.push(PathSegment::Key(rest[..end].to_string()));
+            rest = &rest[end..];
+        } else {
+            return None;
+        }
+        if rest.is_empty() {
+            return Some(segments);
+        }
+        expect_key = match rest.strip_prefix(separator) {
+            Some(after) if !separator.is_empty() => {
+                rest = after;
+                true
+            }
+            _ => false,
+        };
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::JsonValue;
+
+    #[test]
+    fn flatten() {
+        let value = json!([{"a": [[1], []]}, null, {"": {"b": true}}]);
+        let paths: Vec<_> = value.flatten().into_iter().map(|(path, _)| path).collect();
+        assert_eq!(paths, ["[0].a[0][0]", "[0].a[1]", "[1]", "[2]..b"]);
+        assert_eq!(
+            JsonValue::from("x").flatten(),
+            [(String::new(), JsonValue::from("x"))]
+        );
+        assert_eq!(json!({}).flatten(), [(String::new(), json!({}))]);
+    }
+
+    #[test]
+    fn from_flat() {
+        use super::FromFlatError::*;
+
+        let value = json!([{"a": [[1], []]}, null, {"": {"b": true}}, {"c": {}}]);
+        assert_eq!(JsonValue::from_flat(value.flatten()), Ok(value.clone()));
+        assert_eq!(
+            JsonValue::from_flat_with(value.flatten_with("/"), "/"),
+            Ok(value)
+        );
+
+        let from_flat = |pairs: &[(&str, JsonValue)]| {
+            JsonValue::from_flat(pairs.iter().map(|(p, v)| (p.to_string(), v.clone())))
+        };
+        assert_eq!(
+            from_flat(&[
+                ("a[2]", json!(1)),
+                ("a[0]", json!({})),
+                ("a[0].b", json!(2))
+            ]),
+            Ok(json!({"a": [{"b": 2}, null, 1]}))
+        );
+        assert_eq!(from_flat(&[("", json!(1))]), Ok(json!(1)));
+        assert_eq!(
+            from_flat(&[("a.b", json!(1)), ("a[0]", json!(2))]),
+            Err(Conflict("a[0]".to_string()))
+        );
+        assert_eq!(
+            from_flat(&[("a", json!(1)), ("a", json!(1))]),
+            Err(Conflict("a".to_string()))
+        );
+        for path in ["a[x]", "a[0", "a[]", "[0]b"] {
+            assert_eq!(
+                from_flat(&[(path, json!(1))]),
+                Err(InvalidPath(path.to_string()))
+            );
+        }
+    }
+}
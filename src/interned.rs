@@ -0,0 +1,149 @@
+use alloc::{collections::BTreeSet, rc::Rc, string::String, vec::Vec};
+
+use crate::{parse::Event, JsonValue, Map, Number};
+
+/// A parsed JSON value whose strings and object keys are shared, as returned by
+/// [`parse_interned`](crate::parse_interned). Equal strings anywhere in the document point to the
+/// same allocation, which saves memory when the same keys or values repeat many times.
+///
+/// ```
+/// use std::rc::Rc;
+///
+/// use jsnom::{parse_interned, JsonValueRc};
+///
+/// let value = parse_interned("[{\"id\": 1}, {\"id\": 2}]").unwrap();
+/// let JsonValueRc::Array(items) = &value else {
+///     panic!("expected an array");
+/// };
+/// let (JsonValueRc::Object(first), JsonValueRc::Object(second)) = (&items[0], &items[1]) else {
+///     panic!("expected objects");
+/// };
+/// assert!(Rc::ptr_eq(&first[0].0, &second[0].0));
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub enum JsonValueRc {
+    Null,
+    Bool(bool),
+    String(Rc<str>),
+    Array(Vec<JsonValueRc>),
+    Number(Number),
+    Object(Vec<(Rc<str>, JsonValueRc)>),
+}
+
+impl JsonValueRc {
+    /// Convert into a [`JsonValue`], copying each shared string.
+    ///
+    /// ```
+    /// use jsnom::{parse, parse_interned};
+    ///
+    /// let s = "{\"a\": [1, \"a\"]}";
+    /// assert_eq!(parse_interned(s).unwrap().into_owned(), parse(s).unwrap());
+    /// ```
+    pub fn into_owned(self) -> JsonValue {
+        match self {
+            JsonValueRc::Null => JsonValue::Null,
+            JsonValueRc::Bool(b) => JsonValue::Bool(b),
+            JsonValueRc::String(s) => JsonValue::String(String::from(&*s)),
+            JsonValueRc::Array(items) => {
+                JsonValue::Array(items.into_iter().map(Self::into_owned).collect())
+            }
+            JsonValueRc::Number(n) => JsonValue::Number(n),
+            JsonValueRc::Object(entries) => JsonValue::Object(Map::from(
+                entries
+                    .into_iter()
+                    .map(|(key, value)| (String::from(&*key), value.into_owned()))
+                    .collect::<Vec<_>>(),
+            )),
+        }
+    }
+}
+
+impl From<JsonValueRc> for JsonValue {
+    fn from(value: JsonValueRc) -> Self {
+        value.into_owned()
+    }
+}
+
+// Builds a `JsonValueRc` from the events of `Context::events`, keeping one copy of each distinct
+// string seen.
+#[derive(Default)]
+pub(crate) struct Builder {
+    strings: BTreeSet<Rc<str>>,
+    stack: Vec<Partial>,
+    pub(crate) value: Option<JsonValueRc>,
+}
+
+// An array or object that a `Builder` is part way through, along with the key for the next value
+// of an object.
+enum Partial {
+    Array(Vec<JsonValueRc>),
+    Object(Vec<(Rc<str>, JsonValueRc)>, Option<Rc<str>>),
+}
+
+impl Builder {
+    fn intern(&mut self, s: &str) -> Rc<str> {
+        if let Some(shared) = self.strings.get(s) {
+            return Rc::clone(shared);
+        }
+        let shared = Rc::<str>::from(s);
+        self.strings.insert(Rc::clone(&shared));
+        shared
+    }
+
+    pub(crate) fn event(&mut self, event: Event) {
+        let value = match event {
+            Event::Null => JsonValueRc::Null,
+            Event::Bool(b) => JsonValueRc::Bool(b),
+            Event::String(s) => JsonValueRc::String(self.intern(&s)),
+            Event::Number(n) => JsonValueRc::Number(n),
+            Event::BeginArray => return self.stack.push(Partial::Array(Vec::new())),
+            Event::BeginObject => return self.stack.push(Partial::Object(Vec::new(), None)),
+            Event::Key(key) => {
+                let key = self.intern(&key);
+                if let Some(Partial::Object(_, next)) = self.stack.last_mut() {
+                    *next = Some(key);
+                }
+                return;
+            }
+            Event::EndArray | Event::EndObject => match self.stack.pop().unwrap() {
+                Partial::Array(items) => JsonValueRc::Array(items),
+                Partial::Object(entries, _) => JsonValueRc::Object(entries),
+            },
+        };
+        match self.stack.last_mut() {
+            None => self.value = Some(value),
+            Some(Partial::Array(items)) => items.push(value),
+            Some(Partial::Object(entries, key)) => entries.push((key.take().unwrap(), value)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use alloc::rc::Rc;
+
+    use super::JsonValueRc;
+    use crate::parse_interned;
+
+    #[test]
+    fn shares_equal_strings() {
+        let value = parse_interned(r#"{"a": ["a", "a", "b"], "b": null}"#).unwrap();
+        let JsonValueRc::Object(entries) = &value else {
+            panic!("expected an object");
+        };
+        let JsonValueRc::Array(items) = &entries[0].1 else {
+            panic!("expected an array");
+        };
+        let strings: Vec<_> = items
+            .iter()
+            .map(|item| match item {
+                JsonValueRc::String(s) => Rc::clone(s),
+                _ => panic!("expected a string"),
+            })
+            .collect();
+        assert!(Rc::ptr_eq(&entries[0].0, &strings[0]));
+        assert!(Rc::ptr_eq(&strings[0], &strings[1]));
+        assert!(Rc::ptr_eq(&entries[1].0, &strings[2]));
+        assert_eq!(Rc::strong_count(&strings[0]), 5);
+    }
+}
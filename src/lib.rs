@@ -2,7 +2,9 @@
 //!
 //! A small and ergonomic parser library for JSON.
 //!
-//! All parsers provided silently discard any of the string remaining after parser finishes.
+//! All parsers provided silently discard any of the string remaining after parser finishes,
+//! except for [`parse_strict`] which reports it as an error, and [`parse_partial`] which returns
+//! it.
 //!
 //! ## Example
 //!
@@ -18,52 +20,116 @@
 //!     ]))
 //! )
 //! ```
+//!
+//! ## Features
+//!
+//! - `std` (enabled by default): the [`std::error::Error`] implementations and
+//!   [`parse_reader`]. Without it the crate is `no_std`, needing only `alloc`.
+//! - `indexmap`: back [`Map`] with an [`IndexMap`](https://docs.rs/indexmap/latest/indexmap/map/struct.IndexMap.html) for hashed key lookups.
+//! - `serde`: implement `Serialize` and `Deserialize` for [`JsonValue`], [`Number`] and [`Map`].
+//! - `serde_json`: conversions between [`JsonValue`] and `serde_json::Value`.
+//! - `arbitrary`: implement `arbitrary::Arbitrary` for [`JsonValue`] and [`Number`], for fuzzing.
+//! - `rust_decimal`: conversions between [`Number`] and `rust_decimal::Decimal`, for exact
+//!   arithmetic on values such as prices.
+//! - `bumpalo`: [`parse_in`], parsing into a `bumpalo::Bump` arena that is freed all at once.
+//! - `num-bigint`: conversions between [`Number`] and `num_bigint::BigInt`, keeping the digits of
+//!   integers too large for a `u64` or `i64`.
+//!
+//! The `indexmap`, `serde_json` and `arbitrary` features all enable `std`.
+
+#![cfg_attr(not(feature = "std"), no_std)]
 
-use std::fmt;
+extern crate alloc;
 
-use nom::{
-    error::{convert_error, VerboseError, VerboseErrorKind},
-    Finish,
-};
+use alloc::{string::String, vec::Vec};
+use core::str::FromStr;
+#[cfg(feature = "std")]
+use std::io::{self, Read};
 
+use nom::{branch::alt, combinator::all_consuming, error::VerboseError, Finish, IResult};
+
+#[macro_use]
+mod macros;
+
+mod access;
+#[cfg(feature = "arbitrary")]
+mod arbitrary_impls;
+#[cfg(feature = "bumpalo")]
+mod arena;
+#[cfg(feature = "num-bigint")]
+mod bigint_impls;
+mod borrowed;
+mod builder;
+mod compare;
+mod convert;
+#[cfg(feature = "rust_decimal")]
+mod decimal_impls;
+mod edit;
+mod error;
+mod flatten;
+mod interned;
+pub mod map;
+mod number;
+mod options;
 mod parse;
+pub mod parsers;
+mod print;
+mod query;
+mod recover;
+#[cfg(feature = "serde")]
+mod serde_impls;
+#[cfg(feature = "serde_json")]
+mod serde_json_impls;
+mod spanned;
+mod visit;
+mod walk;
+
+// Used by `json!`, so that it works in `no_std` crates.
+#[doc(hidden)]
+pub mod __private {
+    pub use alloc::{string::String, vec};
+}
+
+pub use access::JsonIndex;
+#[cfg(feature = "bumpalo")]
+pub use arena::JsonValueIn;
+pub use borrowed::JsonValueRef;
+pub use builder::{ArrayBuilder, ObjectBuilder};
+pub use compare::Difference;
+pub use convert::TypeError;
+pub use edit::ArrayMerge;
+pub use error::{Error, ErrorKind, OwnedError};
+pub use flatten::FromFlatError;
+pub use interned::JsonValueRc;
+pub use map::Map;
+pub use number::Number;
+pub use options::ParseOptions;
+pub use print::{escape_str, write_escaped};
+pub use spanned::{Spanned, SpannedValue};
+pub use visit::JsonVisitor;
+pub use walk::{JsonStats, PathSegment};
 
 /// Enum representing a parsed JSON input.
-#[derive(Clone, Debug, PartialEq)]
+///
+/// Values implement [`Eq`] and [`Hash`], with objects compared and hashed in key order. See
+/// [`Number`] for how numbers are hashed. They also implement [`Ord`], ordering first by kind and
+/// then by contents, so they can be sorted or used as `BTreeMap` keys.
+///
+/// ```
+/// use std::collections::HashSet;
+/// use jsnom::json;
+///
+/// let records: HashSet<_> = [json!({"id": 1}), json!({"id": 2}), json!({"id": 1})].into();
+/// assert_eq!(records.len(), 2);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub enum JsonValue {
     Null,
     Bool(bool),
     String(String),
     Array(Vec<JsonValue>),
-    Number(f32),
-    Object(Vec<(JsonValue, JsonValue)>),
-}
-
-/// The error type returned from parsers. It is essentially a wrapper around
-/// [`nom::error::VerboseError`] using a different [`std::fmt::Display`].
-#[derive(Clone, Debug, PartialEq)]
-pub struct Error<'a> {
-    pub errors: Vec<(&'a str, VerboseErrorKind)>,
-    data: &'a str,
-    raw_error: VerboseError<&'a str>,
-}
-
-impl<'a> std::error::Error for Error<'a> {}
-
-impl<'a> fmt::Display for Error<'a> {
-    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
-        write!(f, "{}", convert_error(self.data, self.raw_error.clone()))
-    }
-}
-
-impl<'a> Error<'a> {
-    fn from_raw(data: &'a str, raw: VerboseError<&'a str>) -> Self {
-        Self {
-            errors: raw.clone().errors,
-            data,
-            raw_error: raw,
-        }
-    }
+    Number(Number),
+    Object(Map),
 }
 
 impl JsonValue {
@@ -81,48 +147,504 @@ impl JsonValue {
     ///     ]))
     /// )
     /// ```
+    ///
+    /// Unlike the [`FromStr`] implementation, the error borrows from the input.
     #[allow(clippy::should_implement_trait)]
-    // We cannot implement `FromStr` due to lifetimes
-    pub fn from_str(s: &str) -> Result<Self, Error> {
+    pub fn from_str(s: &str) -> Result<Self, Error<'_>> {
         parse(s)
     }
 }
 
+/// Parse a [`JsonValue`] from an input string, the same as [`parse`] but with an owned error.
+///
+/// ```
+/// use jsnom::{JsonValue, OwnedError};
+///
+/// fn parse_owned(s: String) -> Result<JsonValue, OwnedError> {
+///     s.parse()
+/// }
+///
+/// assert_eq!(parse_owned("true".to_string()), Ok(JsonValue::Bool(true)));
+/// assert!(parse_owned("nul".to_string()).is_err());
+/// ```
+impl FromStr for JsonValue {
+    type Err = OwnedError;
+
+    fn from_str(s: &str) -> Result<Self, OwnedError> {
+        parse(s).map_err(OwnedError::from)
+    }
+}
+
 /// Parse a [`JsonValue`] from an input string.
-pub fn parse(s: &str) -> Result<JsonValue, Error> {
+///
+/// A leading UTF-8 byte order mark, as written by some Windows tools, is skipped. This applies to
+/// every function here that parses a whole document.
+///
+/// ```
+/// use jsnom::{parse, JsonValue};
+///
+/// assert_eq!(parse("\u{feff}[null]"), Ok(JsonValue::Array(vec![JsonValue::Null])));
+/// assert!(parse("[\u{feff}null]").is_err());
+/// ```
+pub fn parse(s: &str) -> Result<JsonValue, Error<'_>> {
+    parse_with(s, &ParseOptions::default())
+}
+
+// Skips a leading byte order mark. The error still borrows the original input, which is fine
+// since the rest is a subslice of it.
+fn strip_bom(s: &str) -> &str {
+    s.strip_prefix('\u{feff}').unwrap_or(s)
+}
+
+/// Parse a [`JsonValue`] from an input string, using the given [`ParseOptions`].
+///
+/// ```
+/// use jsnom::{parse_with, JsonValue, ParseOptions};
+///
+/// let opts = ParseOptions {
+///     allow_trailing_commas: true,
+///     ..ParseOptions::default()
+/// };
+/// assert_eq!(
+///     parse_with("[true, false,]", &opts),
+///     Ok(JsonValue::Array(vec![JsonValue::Bool(true), JsonValue::Bool(false)]))
+/// );
+/// ```
+pub fn parse_with<'a>(s: &'a str, opts: &ParseOptions) -> Result<JsonValue, Error<'a>> {
+    let cx = parse::Context::new(opts);
+    parse_whole(&cx, s, |s| cx.value(s))
+}
+
+// Parses a whole document with `parser`, after the checks and skipping that the options ask for
+// on the input as a whole.
+fn parse_whole<'a>(
+    cx: &parse::Context,
+    s: &'a str,
+    parser: impl FnOnce(&'a str) -> IResult<&'a str, JsonValue, VerboseError<&'a str>>,
+) -> Result<JsonValue, Error<'a>> {
+    cx.check_length(s)
+        .and_then(|()| parser(cx.header(strip_bom(s))))
+        .finish()
+        .map(|(_, val)| val)
+        .map_err(|e| Error::from_raw(s, e))
+}
+
+/// Parse a [`JsonValue::Object`] from an input string, keeping the last value for any key repeated
+/// within an object as with [`ParseOptions::dedup_keys`], and also returning the keys that were
+/// repeated. Each appears once, in the order it was first repeated, whichever object it was
+/// repeated in. Any other kind of value fails to parse.
+///
+/// ```
+/// use jsnom::{json, parse_object_report};
+///
+/// let (value, duplicates) =
+///     parse_object_report("{\"id\": 1, \"tags\": {\"x\": 1, \"x\": 2}, \"id\": 2}").unwrap();
+/// assert_eq!(value, json!({"id": 2, "tags": {"x": 2}}));
+/// assert_eq!(duplicates, ["x", "id"]);
+///
+/// let (_, duplicates) = parse_object_report("{\"id\": 1}").unwrap();
+/// assert!(duplicates.is_empty());
+///
+/// assert!(parse_object_report("[1]").is_err());
+/// ```
+pub fn parse_object_report(s: &str) -> Result<(JsonValue, Vec<String>), Error<'_>> {
+    let opts = ParseOptions {
+        dedup_keys: true,
+        ..ParseOptions::default()
+    };
+    let cx = parse::Context::new(&opts);
+    let value = parse_whole(&cx, s, |s| cx.root_object(s))?;
+    Ok((value, cx.take_duplicates()))
+}
+
+/// Parse a [`JsonValue`] from the start of an input string, also returning the rest of the input
+/// after it and any whitespace that follows. This can be used to parse several concatenated values
+/// out of one buffer.
+///
+/// ```
+/// use jsnom::{parse_partial, JsonValue};
+///
+/// let (first, rest) = parse_partial("{\"id\": 1} [2, 3]").unwrap();
+/// assert_eq!(first["id"], JsonValue::from(1));
+/// assert_eq!(rest, "[2, 3]");
+///
+/// let (second, rest) = parse_partial(rest).unwrap();
+/// assert_eq!(second[1], JsonValue::from(3));
+/// assert_eq!(rest, "");
+/// ```
+pub fn parse_partial(s: &str) -> Result<(JsonValue, &str), Error<'_>> {
     parse::nom_parse(s)
+        .finish()
+        .map(|(rest, val)| (val, rest))
+        .map_err(|e| Error::from_raw(s, e))
+}
+
+/// Parse a [`JsonValue`] from the start of an input string, also returning how many bytes of the
+/// input were consumed, including any whitespace after the value. This is the same as
+/// [`parse_partial`], but with an offset for picking up where the value left off.
+///
+/// ```
+/// use jsnom::{parse_consumed, JsonValue};
+///
+/// let frame = "[1, 2] \r\nEND";
+/// let (value, len) = parse_consumed(frame).unwrap();
+/// assert_eq!(value, JsonValue::from(vec![1, 2]));
+/// assert_eq!(&frame[len..], "END");
+/// ```
+pub fn parse_consumed(s: &str) -> Result<(JsonValue, usize), Error<'_>> {
+    parse_partial(s).map(|(val, rest)| (val, s.len() - rest.len()))
+}
+
+/// Parse a sequence of concatenated [`JsonValue`]s, optionally separated by whitespace, until the
+/// input runs out. An empty input gives no values, and anything left over that isn't a valid value
+/// is an error.
+///
+/// ```
+/// use jsnom::{json, parse_many};
+///
+/// let values = parse_many("{\"a\":1}{\"b\":2}\n[1,2]").unwrap();
+/// assert_eq!(values, [json!({"a": 1}), json!({"b": 2}), json!([1, 2])]);
+/// assert_eq!(parse_many("  "), Ok(Vec::new()));
+/// assert!(parse_many("[1] ]").is_err());
+/// ```
+pub fn parse_many(s: &str) -> Result<Vec<JsonValue>, Error<'_>> {
+    let mut values = Vec::new();
+    let mut rest = strip_bom(s);
+    while !rest.trim_start_matches([' ', '\t', '\n', '\r']).is_empty() {
+        let (next, value) = parse::nom_parse(rest)
+            .finish()
+            .map_err(|e| Error::from_raw(s, e))?;
+        values.push(value);
+        rest = next;
+    }
+    Ok(values)
+}
+
+/// Parse a [`JsonValue`] from an input string without recursing into arrays and objects, so that
+/// documents nested deeper than [`ParseOptions::max_depth`] still succeed. The result is the same
+/// as that of [`parse`].
+///
+/// Other operations on the parsed value, including dropping it, do still recurse.
+///
+/// ```
+/// use jsnom::{parse_deep, JsonValue};
+///
+/// let deep = "[".repeat(500) + &"]".repeat(500);
+/// assert!(jsnom::parse(&deep).is_err());
+/// assert!(matches!(parse_deep(&deep), Ok(JsonValue::Array(_))));
+/// ```
+pub fn parse_deep(s: &str) -> Result<JsonValue, Error<'_>> {
+    parse::Context::new(&ParseOptions::default())
+        .deep_value(strip_bom(s))
         .finish()
         .map(|(_, val)| val)
         .map_err(|e| Error::from_raw(s, e))
 }
 
-/// Parse a [`JsonValue::Null`] from an input string.
+/// Parse a [`JsonValueRef`] from an input string, borrowing strings from the input rather than
+/// allocating them wherever they have no escapes. Like [`parse_deep`], this doesn't recurse into
+/// arrays and objects.
+///
+/// ```
+/// use jsnom::{parse_borrowed, JsonValueRef};
+///
+/// let value = parse_borrowed("{\"name\": \"jsnom\"}").unwrap();
+/// assert_eq!(
+///     value,
+///     JsonValueRef::Object(vec![("name".into(), JsonValueRef::String("jsnom".into()))])
+/// );
+/// ```
+pub fn parse_borrowed(s: &str) -> Result<JsonValueRef<'_>, Error<'_>> {
+    let mut builder = borrowed::Builder::default();
+    parse::Context::new(&ParseOptions::default())
+        .events(strip_bom(s), |event| builder.event(event))
+        .finish()
+        .map_err(|e| Error::from_raw(s, e))?;
+    Ok(builder.value.unwrap())
+}
+
+/// Parse a [`JsonValueIn`] from an input string, allocating its strings, arrays and objects in
+/// `bump`. Like [`parse_deep`], this doesn't recurse into arrays and objects.
+///
+/// ```
+/// use bumpalo::Bump;
+/// use jsnom::{parse_in, JsonValueIn};
+///
+/// let bump = Bump::new();
+/// let value = parse_in("[\"a\", \"b\"]", &bump).unwrap();
+/// assert!(matches!(value, JsonValueIn::Array(items) if items[1] == JsonValueIn::String("b")));
+/// ```
+#[cfg(feature = "bumpalo")]
+pub fn parse_in<'a, 'b>(s: &'a str, bump: &'b bumpalo::Bump) -> Result<JsonValueIn<'b>, Error<'a>> {
+    let mut builder = arena::Builder::new(bump);
+    parse::Context::new(&ParseOptions::default())
+        .events(strip_bom(s), |event| builder.event(event))
+        .finish()
+        .map_err(|e| Error::from_raw(s, e))?;
+    Ok(builder.value.unwrap())
+}
+
+/// Parse a [`JsonValueRc`] from an input string, sharing one allocation between all equal strings
+/// and object keys. This suits large documents where the same keys or values repeat many times,
+/// such as arrays of objects with the same fields. Like [`parse_deep`], this doesn't recurse into
+/// arrays and objects.
+///
+/// ```
+/// use jsnom::parse_interned;
+///
+/// let value = parse_interned("[{\"kind\": \"a\"}, {\"kind\": \"a\"}]").unwrap();
+/// assert_eq!(value.into_owned().to_string(), r#"[{"kind":"a"},{"kind":"a"}]"#);
+/// ```
+pub fn parse_interned(s: &str) -> Result<JsonValueRc, Error<'_>> {
+    let mut builder = interned::Builder::default();
+    parse::Context::new(&ParseOptions::default())
+        .events(strip_bom(s), |event| builder.event(event))
+        .finish()
+        .map_err(|e| Error::from_raw(s, e))?;
+    Ok(builder.value.unwrap())
+}
+
+/// Parse an input string, reporting each part of it to `visitor` rather than building a
+/// [`JsonValue`], so that large documents can be processed without holding all of them in memory.
+/// Like [`parse_deep`], this doesn't recurse into arrays and objects.
+///
+/// ```
+/// use jsnom::{parse_events, JsonVisitor};
+///
+/// #[derive(Default)]
+/// struct CountNulls(usize);
+///
+/// impl JsonVisitor for CountNulls {
+///     fn on_null(&mut self) {
+///         self.0 += 1;
+///     }
+/// }
+///
+/// let mut visitor = CountNulls::default();
+/// parse_events("[null, {\"a\": null}, true]", &mut visitor).unwrap();
+/// assert_eq!(visitor.0, 2);
+/// ```
+pub fn parse_events<'a, V: JsonVisitor>(s: &'a str, visitor: &mut V) -> Result<(), Error<'a>> {
+    parse::Context::new(&ParseOptions::default())
+        .events(strip_bom(s), |event| event.visit(visitor))
+        .finish()
+        .map(|(_, ())| ())
+        .map_err(|e| Error::from_raw(s, e))
+}
+
+/// Parse an input string as far as possible, collecting every error found rather than stopping
+/// at the first, as an editor would to show all of the problems at once. After an error, parsing
+/// skips ahead to the next `,`, `]` or `}` and carries on from there; a missing comma or colon is
+/// reported and then treated as if it were present.
+///
+/// Returns the value with anything that failed to parse left out, or `None` if there was no value
+/// at all. As with [`parse_strict`], anything other than whitespace after the value is an error.
+///
+/// ```
+/// use jsnom::{json, parse_recover, ErrorKind};
+///
+/// let (value, errors) = parse_recover("{\"a\": [1 2], \"b\": nul, \"c\": true}");
+/// assert_eq!(value, Some(json!({"a": [1, 2], "c": true})));
+/// assert_eq!(errors.len(), 2);
+/// assert_eq!(errors[0].kind(), ErrorKind::UnexpectedChar('2'));
+/// assert_eq!(errors[1].location(), Some((1, 19)));
+///
+/// assert_eq!(parse_recover("[true]"), (Some(json!([true])), Vec::new()));
+/// ```
+pub fn parse_recover(s: &str) -> (Option<JsonValue>, Vec<Error<'_>>) {
+    recover::Recover::new(s).document(strip_bom(s))
+}
+
+/// Parse an input string into a [`SpannedValue`], which records the byte offsets of every value
+/// and object key in it, for tools such as linters that need to point back into the source.
+///
+/// ```
+/// use jsnom::{parse_spanned, SpannedValue};
+///
+/// let input = "{\"unused\": [1, 2]}";
+/// let root = parse_spanned(input).unwrap();
+/// assert_eq!((root.start, root.end), (0, input.len()));
+///
+/// let SpannedValue::Object(entries) = root.value else {
+///     panic!("expected an object");
+/// };
+/// let (key, value) = &entries[0];
+/// assert_eq!(&input[key.start..key.end], "\"unused\"");
+/// assert_eq!(&input[value.start..value.end], "[1, 2]");
+/// ```
+pub fn parse_spanned(s: &str) -> Result<Spanned<SpannedValue>, Error<'_>> {
+    parse::Context::new(&ParseOptions::default())
+        .spanned(s, strip_bom(s))
+        .finish()
+        .map(|(_, val)| val)
+        .map_err(|e| Error::from_raw(s, e))
+}
+
+/// Parse a [`JsonValue`] from an input string, returning an error if anything other than
+/// whitespace remains after the value.
+///
+/// ```
+/// use jsnom::{parse_strict, JsonValue};
+///
+/// assert_eq!(parse_strict(" true "), Ok(JsonValue::Bool(true)));
+/// assert!(parse_strict("true garbage").is_err());
+/// ```
+pub fn parse_strict(s: &str) -> Result<JsonValue, Error<'_>> {
+    all_consuming(parse::nom_parse)(strip_bom(s))
+        .finish()
+        .map(|(_, val)| val)
+        .map_err(|e| Error::from_raw(s, e))
+}
+
+/// Check that an input string is a single valid JSON value, with nothing but whitespace after it,
+/// without building a [`JsonValue`]. Like [`parse_deep`], this doesn't recurse into arrays and
+/// objects.
+///
+/// ```
+/// use jsnom::validate;
+///
+/// assert_eq!(validate("{\"a\": [1, 2]}"), Ok(()));
+/// assert!(validate("{\"a\": [1, 2}").is_err());
+/// assert!(validate("true garbage").is_err());
+/// ```
+pub fn validate(s: &str) -> Result<(), Error<'_>> {
+    let opts = ParseOptions::default();
+    let cx = parse::Context::new(&opts);
+    let result = all_consuming(|s| cx.events(s, |_| ()))(strip_bom(s))
+        .finish()
+        .map(|(_, ())| ())
+        .map_err(|e| Error::from_raw(s, e));
+    result
+}
+
+/// Parse newline-delimited JSON (also known as JSONL or NDJSON), with one value per line. Blank
+/// lines are skipped, and each line is parsed as by [`parse_strict`], so that a bad line only
+/// produces an error for that line. Error locations are relative to the start of the line.
+///
+/// ```
+/// use jsnom::{parse_lines, JsonValue};
+///
+/// let lines: Vec<_> = parse_lines("{\"a\": 1}\n\nnul\n[true]\n").collect();
+/// assert_eq!(lines.len(), 3);
+/// assert_eq!(lines[0].as_ref().unwrap()["a"], JsonValue::from(1));
+/// assert!(lines[1].is_err());
+/// assert_eq!(lines[2], Ok(JsonValue::Array(vec![JsonValue::Bool(true)])));
+/// ```
+pub fn parse_lines(s: &str) -> impl Iterator<Item = Result<JsonValue, OwnedError>> + '_ {
+    s.lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| parse_strict(line).map_err(OwnedError::from))
+}
+
+/// Parse a JSON document whose root is an array or object, as some consumers require, returning an
+/// error for a bare scalar or if anything other than whitespace remains after the value.
+///
+/// ```
+/// use jsnom::{parse_document, JsonValue};
+///
+/// assert_eq!(parse_document(" [true] "), Ok(JsonValue::Array(vec![JsonValue::Bool(true)])));
+/// assert!(parse_document("true").is_err());
+/// assert!(parse_document("{} {}").is_err());
+/// ```
+pub fn parse_document(s: &str) -> Result<JsonValue, Error<'_>> {
+    all_consuming(alt((parse::nom_array, parse::nom_object)))(strip_bom(s))
+        .finish()
+        .map(|(_, val)| val)
+        .map_err(|e| Error::from_raw(s, e))
+}
+
+/// Parse a [`JsonValue`] from UTF-8 encoded bytes, returning an error if they are not valid
+/// UTF-8.
+///
+/// ```
+/// use jsnom::{parse_bytes, JsonValue};
+///
+/// assert_eq!(parse_bytes(b"[null]"), Ok(JsonValue::Array(vec![JsonValue::Null])));
+/// assert!(parse_bytes(b"\"\xff\"").is_err());
+/// ```
+pub fn parse_bytes(b: &[u8]) -> Result<JsonValue, OwnedError> {
+    let s = core::str::from_utf8(b).map_err(OwnedError::invalid_utf8)?;
+    parse(s).map_err(OwnedError::from)
+}
+
+/// Read the whole of `r` and parse a [`JsonValue`] from it.
+///
+/// Invalid UTF-8 and parse failures are reported as an [`io::Error`] of kind
+/// [`io::ErrorKind::InvalidData`], wrapping an [`OwnedError`].
+///
+/// ```
+/// use jsnom::{parse_reader, JsonValue};
+///
+/// let file: &[u8] = b"{\"ok\": true}";
+/// assert_eq!(parse_reader(file).unwrap()["ok"], JsonValue::Bool(true));
+/// ```
+#[cfg(feature = "std")]
+pub fn parse_reader<R: Read>(mut r: R) -> io::Result<JsonValue> {
+    let mut buf = Vec::new();
+    r.read_to_end(&mut buf)?;
+    parse_bytes(&buf).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+/// Parse a [`JsonValue::Null`] from an input string. Whitespace around it is skipped; see
+/// [`parse_null_exact`] for a parser that matches only `null` itself.
 ///
 /// ```
 /// use jsnom::{parse_null, JsonValue};
 ///
 /// assert_eq!(parse_null("null"), Ok(JsonValue::Null));
 /// ```
-pub fn parse_null(s: &str) -> Result<JsonValue, Error> {
+pub fn parse_null(s: &str) -> Result<JsonValue, Error<'_>> {
     parse::nom_null(s)
         .finish()
         .map(|(_, val)| val)
         .map_err(|e| Error::from_raw(s, e))
 }
 
-/// Parse a [`JsonValue::Bool`] from an input string.
+/// Parse a [`JsonValue::Null`] from the very start of an input string, also returning the rest of
+/// the input after it, including any whitespace.
+///
+/// ```
+/// use jsnom::{parse_null_exact, JsonValue};
+///
+/// assert_eq!(parse_null_exact("null "), Ok((JsonValue::Null, " ")));
+/// assert!(parse_null_exact(" null").is_err());
+/// ```
+pub fn parse_null_exact(s: &str) -> Result<(JsonValue, &str), Error<'_>> {
+    parse::nom_null_exact(s)
+        .finish()
+        .map(|(rest, val)| (val, rest))
+        .map_err(|e| Error::from_raw(s, e))
+}
+
+/// Parse a [`JsonValue::Bool`] from an input string. Whitespace around it is skipped; see
+/// [`parse_bool_exact`] for a parser that matches only the `true` or `false` itself.
 /// ```
 /// use jsnom::{parse_bool, JsonValue};
 ///
 /// assert_eq!(parse_bool("true"), Ok(JsonValue::Bool(true)));
 /// ```
-pub fn parse_bool(s: &str) -> Result<JsonValue, Error> {
+pub fn parse_bool(s: &str) -> Result<JsonValue, Error<'_>> {
     parse::nom_bool(s)
         .finish()
         .map(|(_, val)| val)
         .map_err(|e| Error::from_raw(s, e))
 }
 
+/// Parse a [`JsonValue::Bool`] from the very start of an input string, also returning the rest of
+/// the input after it, including any whitespace.
+/// ```
+/// use jsnom::{parse_bool_exact, JsonValue};
+///
+/// assert_eq!(parse_bool_exact("false\n"), Ok((JsonValue::Bool(false), "\n")));
+/// assert!(parse_bool_exact("\ttrue").is_err());
+/// ```
+pub fn parse_bool_exact(s: &str) -> Result<(JsonValue, &str), Error<'_>> {
+    parse::nom_bool_exact(s)
+        .finish()
+        .map(|(rest, val)| (val, rest))
+        .map_err(|e| Error::from_raw(s, e))
+}
+
 /// Parse a [`JsonValue::String`] from an input string.
 /// ```
 /// use jsnom::{parse_string, JsonValue};
@@ -132,13 +654,30 @@ pub fn parse_bool(s: &str) -> Result<JsonValue, Error> {
 ///     Ok(JsonValue::String("Hello, world!\n".to_string()))
 /// );
 /// ```
-pub fn parse_string(s: &str) -> Result<JsonValue, Error> {
+pub fn parse_string(s: &str) -> Result<JsonValue, Error<'_>> {
     parse::nom_string(s)
         .finish()
         .map(|(_, val)| val)
         .map_err(|e| Error::from_raw(s, e))
 }
 
+/// Decode the contents of a JSON string literal, without its surrounding quotes, using the same
+/// rules as the parser. This is the reverse of [`escape_str`].
+///
+/// ```
+/// use jsnom::unescape_str;
+///
+/// assert_eq!(unescape_str("tab\\there \\ud83d\\ude00"), Ok("tab\there \u{1f600}".to_string()));
+/// assert!(unescape_str("bad \\x escape").is_err());
+/// assert!(unescape_str("unescaped \" quote").is_err());
+/// ```
+pub fn unescape_str(s: &str) -> Result<String, Error<'_>> {
+    all_consuming(parse::nom_string_contents)(s)
+        .finish()
+        .map(|(_, contents)| contents.into_owned())
+        .map_err(|e| Error::from_raw(s, e))
+}
+
 /// Parse a [`JsonValue::Array`] from an input string.
 /// ```
 /// use jsnom::{parse_array, JsonValue};
@@ -155,26 +694,42 @@ pub fn parse_string(s: &str) -> Result<JsonValue, Error> {
 ///     ]))
 /// );
 /// ```
-pub fn parse_array(s: &str) -> Result<JsonValue, Error> {
+pub fn parse_array(s: &str) -> Result<JsonValue, Error<'_>> {
     parse::nom_array(s)
         .finish()
         .map(|(_, val)| val)
         .map_err(|e| Error::from_raw(s, e))
 }
 
-/// Parse a [`JsonValue::Number`] from an input string.
+/// Parse a [`JsonValue::Number`] from an input string. Whitespace around the number is skipped;
+/// see [`parse_number_exact`] for a parser that matches only the number itself.
 /// ```
 /// use jsnom::{parse_number, JsonValue};
 ///
-/// assert_eq!(parse_number("-3e-2"), Ok(JsonValue::Number(-0.03)));
+/// assert_eq!(parse_number("-3e-2"), Ok(JsonValue::Number((-0.03).into())));
 /// ```
-pub fn parse_number(s: &str) -> Result<JsonValue, Error> {
+pub fn parse_number(s: &str) -> Result<JsonValue, Error<'_>> {
     parse::nom_number(s)
         .finish()
         .map(|(_, val)| val)
         .map_err(|e| Error::from_raw(s, e))
 }
 
+/// Parse a [`JsonValue::Number`] from the very start of an input string, also returning the rest
+/// of the input after it, including any whitespace.
+/// ```
+/// use jsnom::{parse_number_exact, JsonValue};
+///
+/// assert_eq!(parse_number_exact("3 "), Ok((JsonValue::from(3), " ")));
+/// assert!(parse_number_exact(" 3").is_err());
+/// ```
+pub fn parse_number_exact(s: &str) -> Result<(JsonValue, &str), Error<'_>> {
+    parse::nom_number_exact(s)
+        .finish()
+        .map(|(rest, val)| (val, rest))
+        .map_err(|e| Error::from_raw(s, e))
+}
+
 /// Parse a [`JsonValue::Object`] from an input string.
 /// ```
 /// use jsnom::{parse_object, JsonValue::{self, *}};
@@ -182,15 +737,15 @@ pub fn parse_number(s: &str) -> Result<JsonValue, Error> {
 /// assert_eq!(
 ///     parse_object("{\"user\": \"Piturnah\", \"crates\": [\"gex\", \"newdoku\", \"jsnom\"]}"),
 ///     Ok(JsonValue::Object(vec![
-///         (String("user".to_string()), String("Piturnah".to_string())),
-///         (String("crates".to_string()), Array(vec![
+///         ("user".to_string(), String("Piturnah".to_string())),
+///         ("crates".to_string(), Array(vec![
 ///             String("gex".to_string()),
 ///             String("newdoku".to_string()),
 ///             String("jsnom".to_string()),
 ///         ]))
-///     ])));
+///     ].into())));
 /// ```
-pub fn parse_object(s: &str) -> Result<JsonValue, Error> {
+pub fn parse_object(s: &str) -> Result<JsonValue, Error<'_>> {
     parse::nom_object(s)
         .finish()
         .map(|(_, val)| val)
@@ -0,0 +1,105 @@
+/// Construct a [`JsonValue`](crate::JsonValue) from near-literal JSON syntax.
+///
+/// Keys must be string literals, variables, or parenthesized expressions, and are converted with
+/// `String::from`. Any value that isn't `null`, `true`, `false` or a nested array or object is
+/// treated as a Rust expression and converted with `JsonValue::from`.
+///
+/// ```
+/// use jsnom::{json, JsonValue};
+///
+/// let name = "jsnom";
+/// let value = json!({
+///     "name": name,
+///     "version": [1, 0, 1],
+///     "published": true,
+///     "size": 2.5 * 2.0,
+///     "license": null,
+/// });
+/// assert_eq!(
+///     value.to_string(),
+///     "{\"name\":\"jsnom\",\"version\":[1,0,1],\"published\":true,\"size\":5,\"license\":null}"
+/// );
+/// ```
+#[macro_export]
+macro_rules! json {
+    // Munch the tokens of an array one at a time, converting each element when a comma or the end
+    // of the input is reached.
+    (@array [$($done:expr,)*] () ()) => {
+        $crate::__private::vec![$($done,)*]
+    };
+    (@array [$($done:expr,)*] ($($elem:tt)+) ()) => {
+        $crate::__private::vec![$($done,)* $crate::json!($($elem)+)]
+    };
+    (@array [$($done:expr,)*] ($($elem:tt)+) (, $($rest:tt)*)) => {
+        $crate::json!(@array [$($done,)* $crate::json!($($elem)+),] () ($($rest)*))
+    };
+    (@array [$($done:expr,)*] ($($elem:tt)*) ($next:tt $($rest:tt)*)) => {
+        $crate::json!(@array [$($done,)*] ($($elem)* $next) ($($rest)*))
+    };
+
+    // Likewise for objects, first taking the key and then munching the value.
+    (@object $object:ident () ()) => {};
+    (@object $object:ident () ($key:tt : $($rest:tt)*)) => {
+        $crate::json!(@object $object [$key] () ($($rest)*));
+    };
+    (@object $object:ident [$key:tt] ($($value:tt)+) ()) => {
+        $object.insert($crate::__private::String::from($key), $crate::json!($($value)+));
+    };
+    (@object $object:ident [$key:tt] ($($value:tt)+) (, $($rest:tt)*)) => {
+        $object.insert($crate::__private::String::from($key), $crate::json!($($value)+));
+        $crate::json!(@object $object () ($($rest)*));
+    };
+    (@object $object:ident [$key:tt] ($($value:tt)*) ($next:tt $($rest:tt)*)) => {
+        $crate::json!(@object $object [$key] ($($value)* $next) ($($rest)*));
+    };
+
+    (null) => {
+        $crate::JsonValue::Null
+    };
+    (true) => {
+        $crate::JsonValue::Bool(true)
+    };
+    (false) => {
+        $crate::JsonValue::Bool(false)
+    };
+    ([ $($tt:tt)* ]) => {
+        $crate::JsonValue::Array($crate::json!(@array [] () ($($tt)*)))
+    };
+    ({ $($tt:tt)* }) => {{
+        #[allow(unused_mut)]
+        let mut object = $crate::Map::new();
+        $crate::json!(@object object () ($($tt)*));
+        $crate::JsonValue::Object(object)
+    }};
+    ($other:expr) => {
+        $crate::JsonValue::from($other)
+    };
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{JsonValue, Map, Number};
+
+    #[test]
+    fn scalars() {
+        assert_eq!(json!(null), JsonValue::Null);
+        assert_eq!(json!(false), JsonValue::Bool(false));
+        assert_eq!(json!(-1), JsonValue::Number(Number::from(-1i64)));
+        assert_eq!(json!("a"), JsonValue::String("a".to_string()));
+    }
+
+    #[test]
+    fn empty() {
+        assert_eq!(json!([]), JsonValue::Array(Vec::new()));
+        assert_eq!(json!({}), JsonValue::Object(Map::new()));
+    }
+
+    #[test]
+    fn nested() {
+        let key = "b";
+        assert_eq!(
+            json!({"a": [1, [], {}], (key): {"c": -2.5}, "d": 1 + 1,}),
+            JsonValue::from_str("{\"a\": [1, [], {}], \"b\": {\"c\": -2.5}, \"d\": 2}").unwrap()
+        );
+    }
+}
@@ -0,0 +1,459 @@
+//! The [`Map`] type backing [`JsonValue::Object`].
+//!
+//! By default a [`Map`] is a `Vec` of entries, so lookups scan every key. Enabling the `indexmap`
+//! feature backs it with an [`IndexMap`](https://docs.rs/indexmap/latest/indexmap/map/struct.IndexMap.html) instead, giving hashed lookups while still
+//! preserving insertion order.
+
+use alloc::{string::String, vec::Vec};
+use core::{
+    cmp::Ordering,
+    fmt,
+    hash::{Hash, Hasher},
+    iter::FusedIterator,
+};
+
+use crate::JsonValue;
+
+#[cfg(not(feature = "indexmap"))]
+type MapImpl = Vec<(String, JsonValue)>;
+#[cfg(feature = "indexmap")]
+type MapImpl = indexmap::IndexMap<String, JsonValue>;
+
+/// The entries of a JSON object, in the order they were inserted.
+///
+/// Lookups always see the last entry for a key, as with
+/// [`ParseOptions::dedup_keys`](crate::ParseOptions::dedup_keys). Without the `indexmap` feature,
+/// repeated keys are kept as separate entries; with it, a repeat replaces the earlier value when
+/// the map is built, keeping the key's first position.
+#[derive(Clone, Default)]
+pub struct Map {
+    inner: MapImpl,
+}
+
+impl Map {
+    /// Creates an empty map.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates an empty map with space for at least `capacity` entries.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            inner: MapImpl::with_capacity(capacity),
+        }
+    }
+
+    /// Returns the number of entries in the map.
+    pub fn len(&self) -> usize {
+        self.inner.len()
+    }
+
+    /// Returns `true` if the map has no entries.
+    pub fn is_empty(&self) -> bool {
+        self.inner.is_empty()
+    }
+
+    /// Removes every entry from the map.
+    pub fn clear(&mut self) {
+        self.inner.clear();
+    }
+
+    /// Returns the value for `key`, if present.
+    ///
+    /// ```
+    /// use jsnom::{JsonValue, Map};
+    ///
+    /// let map = Map::from(vec![("a".to_string(), JsonValue::Null)]);
+    /// assert_eq!(map.get("a"), Some(&JsonValue::Null));
+    /// assert_eq!(map.get("b"), None);
+    /// ```
+    pub fn get(&self, key: &str) -> Option<&JsonValue> {
+        #[cfg(not(feature = "indexmap"))]
+        return self
+            .inner
+            .iter()
+            .rev()
+            .find(|(k, _)| k == key)
+            .map(|(_, v)| v);
+        #[cfg(feature = "indexmap")]
+        return self.inner.get(key);
+    }
+
+    /// Returns a mutable reference to the value for `key`, if present.
+    pub fn get_mut(&mut self, key: &str) -> Option<&mut JsonValue> {
+        #[cfg(not(feature = "indexmap"))]
+        return self
+            .inner
+            .iter_mut()
+            .rev()
+            .find(|(k, _)| k == key)
+            .map(|(_, v)| v);
+        #[cfg(feature = "indexmap")]
+        return self.inner.get_mut(key);
+    }
+
+    /// Returns `true` if the map has an entry for `key`.
+    pub fn contains_key(&self, key: &str) -> bool {
+        self.get(key).is_some()
+    }
+
+    /// Sets the value for `key`, returning the previous value if there was one. A new key is
+    /// added at the end of the map; an existing key keeps its position.
+    ///
+    /// ```
+    /// use jsnom::{JsonValue, Map};
+    ///
+    /// let mut map = Map::new();
+    /// assert_eq!(map.insert("a".to_string(), JsonValue::Null), None);
+    /// assert_eq!(
+    ///     map.insert("a".to_string(), JsonValue::Bool(true)),
+    ///     Some(JsonValue::Null)
+    /// );
+    /// assert_eq!(map.len(), 1);
+    /// ```
+    pub fn insert(&mut self, key: String, value: JsonValue) -> Option<JsonValue> {
+        #[cfg(not(feature = "indexmap"))]
+        return match self.get_mut(&key) {
+            Some(existing) => Some(core::mem::replace(existing, value)),
+            None => {
+                self.inner.push((key, value));
+                None
+            }
+        };
+        #[cfg(feature = "indexmap")]
+        return self.inner.insert(key, value);
+    }
+
+    /// Removes `key` from the map, returning its value if it was present. The order of the
+    /// remaining entries is preserved.
+    pub fn remove(&mut self, key: &str) -> Option<JsonValue> {
+        #[cfg(not(feature = "indexmap"))]
+        {
+            let i = self.inner.iter().rposition(|(k, _)| k == key)?;
+            let (_, value) = self.inner.remove(i);
+            // Drop any repeats too, so that the key doesn't reappear.
+            self.inner.retain(|(k, _)| k != key);
+            Some(value)
+        }
+        #[cfg(feature = "indexmap")]
+        return self.inner.shift_remove(key);
+    }
+
+    /// Keeps only the entries for which `f` returns `true`.
+    pub fn retain<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&String, &mut JsonValue) -> bool,
+    {
+        #[cfg(not(feature = "indexmap"))]
+        self.inner.retain_mut(|(k, v)| f(k, v));
+        #[cfg(feature = "indexmap")]
+        self.inner.retain(|k, v| f(k, v));
+    }
+
+    /// Sorts the entries by key. The sort is stable, so repeated keys keep their order.
+    pub fn sort_keys(&mut self) {
+        #[cfg(not(feature = "indexmap"))]
+        self.inner.sort_by(|(a, _), (b, _)| a.cmp(b));
+        #[cfg(feature = "indexmap")]
+        self.inner.sort_keys();
+    }
+
+    /// Returns an iterator over the entries of the map, in order.
+    pub fn iter(&self) -> Iter<'_> {
+        Iter {
+            inner: self.inner.iter(),
+        }
+    }
+
+    /// Returns an iterator over the entries of the map, in order, with mutable references to
+    /// the values.
+    pub fn iter_mut(&mut self) -> IterMut<'_> {
+        IterMut {
+            inner: self.inner.iter_mut(),
+        }
+    }
+
+    /// Returns an iterator over the keys of the map, in order.
+    pub fn keys(&self) -> impl DoubleEndedIterator<Item = &String> + ExactSizeIterator {
+        self.iter().map(|(k, _)| k)
+    }
+
+    /// Returns an iterator over the values of the map, in order.
+    pub fn values(&self) -> impl DoubleEndedIterator<Item = &JsonValue> + ExactSizeIterator {
+        self.iter().map(|(_, v)| v)
+    }
+
+    /// Returns an iterator over mutable references to the values of the map, in order.
+    pub fn values_mut(
+        &mut self,
+    ) -> impl DoubleEndedIterator<Item = &mut JsonValue> + ExactSizeIterator {
+        self.iter_mut().map(|(_, v)| v)
+    }
+}
+
+// Equality is order-sensitive with either backing, matching comparison of the entries as a list.
+impl PartialEq for Map {
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len() && self.iter().eq(other.iter())
+    }
+}
+
+impl Eq for Map {}
+
+// Ordered by comparing the entries in turn, to agree with `PartialEq`.
+impl Ord for Map {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.iter().cmp(other.iter())
+    }
+}
+
+impl PartialOrd for Map {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+// Hashed in order to agree with `PartialEq`.
+impl Hash for Map {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        state.write_usize(self.len());
+        for entry in self.iter() {
+            entry.hash(state);
+        }
+    }
+}
+
+impl fmt::Debug for Map {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl From<Vec<(String, JsonValue)>> for Map {
+    fn from(entries: Vec<(String, JsonValue)>) -> Self {
+        #[cfg(not(feature = "indexmap"))]
+        return Self { inner: entries };
+        #[cfg(feature = "indexmap")]
+        return entries.into_iter().collect();
+    }
+}
+
+impl FromIterator<(String, JsonValue)> for Map {
+    fn from_iter<T: IntoIterator<Item = (String, JsonValue)>>(iter: T) -> Self {
+        let mut map = Map::new();
+        map.extend(iter);
+        map
+    }
+}
+
+impl Extend<(String, JsonValue)> for Map {
+    fn extend<T: IntoIterator<Item = (String, JsonValue)>>(&mut self, iter: T) {
+        #[cfg(not(feature = "indexmap"))]
+        self.inner.extend(iter);
+        #[cfg(feature = "indexmap")]
+        for (key, value) in iter {
+            self.inner.insert(key, value);
+        }
+    }
+}
+
+impl IntoIterator for Map {
+    type Item = (String, JsonValue);
+    type IntoIter = IntoIter;
+
+    fn into_iter(self) -> IntoIter {
+        IntoIter {
+            inner: self.inner.into_iter(),
+        }
+    }
+}
+
+impl<'a> IntoIterator for &'a Map {
+    type Item = (&'a String, &'a JsonValue);
+    type IntoIter = Iter<'a>;
+
+    fn into_iter(self) -> Iter<'a> {
+        self.iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a mut Map {
+    type Item = (&'a String, &'a mut JsonValue);
+    type IntoIter = IterMut<'a>;
+
+    fn into_iter(self) -> IterMut<'a> {
+        self.iter_mut()
+    }
+}
+
+#[cfg(not(feature = "indexmap"))]
+type IterImpl<'a> = core::slice::Iter<'a, (String, JsonValue)>;
+#[cfg(feature = "indexmap")]
+type IterImpl<'a> = indexmap::map::Iter<'a, String, JsonValue>;
+
+#[cfg(not(feature = "indexmap"))]
+type IterMutImpl<'a> = core::slice::IterMut<'a, (String, JsonValue)>;
+#[cfg(feature = "indexmap")]
+type IterMutImpl<'a> = indexmap::map::IterMut<'a, String, JsonValue>;
+
+#[cfg(not(feature = "indexmap"))]
+type IntoIterImpl = alloc::vec::IntoIter<(String, JsonValue)>;
+#[cfg(feature = "indexmap")]
+type IntoIterImpl = indexmap::map::IntoIter<String, JsonValue>;
+
+/// An iterator over the entries of a [`Map`], created by [`Map::iter`].
+#[derive(Clone, Debug)]
+pub struct Iter<'a> {
+    inner: IterImpl<'a>,
+}
+
+impl<'a> Iterator for Iter<'a> {
+    type Item = (&'a String, &'a JsonValue);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        #[cfg(not(feature = "indexmap"))]
+        return self.inner.next().map(|(k, v)| (k, v));
+        #[cfg(feature = "indexmap")]
+        return self.inner.next();
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl DoubleEndedIterator for Iter<'_> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        #[cfg(not(feature = "indexmap"))]
+        return self.inner.next_back().map(|(k, v)| (k, v));
+        #[cfg(feature = "indexmap")]
+        return self.inner.next_back();
+    }
+}
+
+impl ExactSizeIterator for Iter<'_> {}
+impl FusedIterator for Iter<'_> {}
+
+/// A mutable iterator over the entries of a [`Map`], created by [`Map::iter_mut`].
+#[derive(Debug)]
+pub struct IterMut<'a> {
+    inner: IterMutImpl<'a>,
+}
+
+impl<'a> Iterator for IterMut<'a> {
+    type Item = (&'a String, &'a mut JsonValue);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        #[cfg(not(feature = "indexmap"))]
+        return self.inner.next().map(|(k, v)| (&*k, v));
+        #[cfg(feature = "indexmap")]
+        return self.inner.next();
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl DoubleEndedIterator for IterMut<'_> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        #[cfg(not(feature = "indexmap"))]
+        return self.inner.next_back().map(|(k, v)| (&*k, v));
+        #[cfg(feature = "indexmap")]
+        return self.inner.next_back();
+    }
+}
+
+impl ExactSizeIterator for IterMut<'_> {}
+impl FusedIterator for IterMut<'_> {}
+
+/// An owning iterator over the entries of a [`Map`].
+#[derive(Debug)]
+pub struct IntoIter {
+    inner: IntoIterImpl,
+}
+
+impl Iterator for IntoIter {
+    type Item = (String, JsonValue);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl DoubleEndedIterator for IntoIter {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.inner.next_back()
+    }
+}
+
+impl ExactSizeIterator for IntoIter {}
+impl FusedIterator for IntoIter {}
+
+#[cfg(test)]
+mod test {
+    use super::Map;
+    use crate::JsonValue::*;
+
+    #[test]
+    fn get_sees_last_entry() {
+        let map = Map::from_iter([("a".to_string(), Null), ("a".to_string(), Bool(true))]);
+        assert_eq!(map.get("a"), Some(&Bool(true)));
+    }
+
+    #[test]
+    fn remove_sees_last_entry() {
+        let mut map = Map::from_iter([
+            ("a".to_string(), Null),
+            ("b".to_string(), Null),
+            ("a".to_string(), Bool(true)),
+        ]);
+        assert_eq!(map.remove("a"), Some(Bool(true)));
+        assert_eq!(map.get("a"), None);
+        assert_eq!(map.len(), 1);
+    }
+
+    // Runs under both backends, which must agree on repeated keys.
+    #[test]
+    fn duplicate_keys_agree_across_backends() {
+        use crate::{parse, JsonValue};
+
+        let mut value = parse("{\"a\": 1, \"b\": 2, \"a\": 3}").unwrap();
+        assert_eq!(value["a"], JsonValue::from(3));
+        assert_eq!(value.get("a"), Some(&JsonValue::from(3)));
+        assert_eq!(value.keys().unwrap()[0], "a");
+        let JsonValue::Object(map) = &mut value else {
+            panic!("expected an object");
+        };
+        assert_eq!(map.get_mut("a"), Some(&mut JsonValue::from(3)));
+        assert_eq!(map.remove("a"), Some(JsonValue::from(3)));
+        assert_eq!(map.get("a"), None);
+        assert_eq!(value, parse("{\"b\": 2}").unwrap());
+    }
+
+    #[test]
+    fn remove_preserves_order() {
+        let mut map = Map::from_iter([
+            ("a".to_string(), Null),
+            ("b".to_string(), Bool(true)),
+            ("c".to_string(), Bool(false)),
+        ]);
+        assert_eq!(map.remove("b"), Some(Bool(true)));
+        assert_eq!(map.remove("b"), None);
+        assert_eq!(
+            map.into_iter().collect::<Vec<_>>(),
+            vec![("a".to_string(), Null), ("c".to_string(), Bool(false))]
+        );
+    }
+
+    #[test]
+    fn eq_is_order_sensitive() {
+        let ab = Map::from_iter([("a".to_string(), Null), ("b".to_string(), Null)]);
+        let ba = Map::from_iter([("b".to_string(), Null), ("a".to_string(), Null)]);
+        assert_ne!(ab, ba);
+    }
+}
@@ -0,0 +1,336 @@
+use alloc::{boxed::Box, format};
+use core::{
+    cmp::Ordering,
+    fmt,
+    hash::{Hash, Hasher},
+};
+
+/// A JSON number, remembering whether it was written as an integer or a float.
+///
+/// Integer literals that fit in a `u64` or `i64` are stored exactly, while anything with a
+/// fractional part or exponent, or that is too large in magnitude, is stored as an `f64`. Numbers
+/// compare by value whichever way they are stored, so `1` and `1.0` are equal.
+///
+/// JSON has no `NaN` or `Infinity`, so those literals are rejected by the parser, as is any number
+/// too large in magnitude for an `f64`, such as `1e400`.
+///
+/// ```
+/// use jsnom::parse_number;
+///
+/// let id = parse_number("9007199254740993").unwrap();
+/// assert_eq!(id.as_u64(), Some(9007199254740993));
+///
+/// let float = parse_number("1.5").unwrap();
+/// assert_eq!(float.as_u64(), None);
+/// assert_eq!(float.as_f64(), Some(1.5));
+///
+/// assert!(parse_number("1e400").is_err());
+/// ```
+///
+/// Numbers implement [`Eq`] and [`Hash`], so values can be used as set or map keys. A whole float
+/// hashes the same as the equal integer, and `-0.0` the same as `0.0`. So that equality is total,
+/// every `NaN` built with `Number::from(f64::NAN)` is equal to every other, unlike with `f64`; the
+/// parser never produces one.
+///
+/// With [`ParseOptions::preserve_number_literals`](crate::ParseOptions::preserve_number_literals),
+/// the text a number was parsed from is kept too, and is what gets written back out.
+#[derive(Clone)]
+pub struct Number {
+    n: N,
+    literal: Option<Box<str>>,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum N {
+    PosInt(u64),
+    /// Always less than zero.
+    NegInt(i64),
+    Float(f64),
+}
+
+impl Number {
+    pub(crate) fn with_literal(self, literal: &str) -> Self {
+        Self {
+            literal: Some(literal.into()),
+            ..self
+        }
+    }
+
+    /// Returns the text the number was parsed from, if it was parsed with
+    /// [`ParseOptions::preserve_number_literals`](crate::ParseOptions::preserve_number_literals)
+    /// set.
+    ///
+    /// ```
+    /// use jsnom::{parse_with, ParseOptions};
+    ///
+    /// let opts = ParseOptions {
+    ///     preserve_number_literals: true,
+    ///     ..ParseOptions::default()
+    /// };
+    /// let value = parse_with("[1.50, 1E3]", &opts).unwrap();
+    /// assert_eq!(value[0].as_number().unwrap().literal(), Some("1.50"));
+    /// assert_eq!(value.to_string(), "[1.50,1E3]");
+    /// ```
+    pub fn literal(&self) -> Option<&str> {
+        self.literal.as_deref()
+    }
+
+    /// Returns `true` if the number is an integer that fits in an `i64`.
+    pub fn is_i64(&self) -> bool {
+        self.as_i64().is_some()
+    }
+
+    /// Returns `true` if the number is an integer that fits in a `u64`.
+    pub fn is_u64(&self) -> bool {
+        self.as_u64().is_some()
+    }
+
+    /// Returns `true` if the number is stored as a float.
+    pub fn is_f64(&self) -> bool {
+        matches!(self.n, N::Float(_))
+    }
+
+    /// Returns the number as an `i64` if it is an integer in range.
+    ///
+    /// ```
+    /// use jsnom::Number;
+    ///
+    /// assert_eq!(Number::from(-3i64).as_i64(), Some(-3));
+    /// assert_eq!(Number::from(u64::MAX).as_i64(), None);
+    /// assert_eq!(Number::from(3.0).as_i64(), None);
+    /// ```
+    pub fn as_i64(&self) -> Option<i64> {
+        match self.n {
+            N::PosInt(n) => i64::try_from(n).ok(),
+            N::NegInt(n) => Some(n),
+            N::Float(_) => None,
+        }
+    }
+
+    /// Returns the number as a `u64` if it is a non-negative integer in range.
+    ///
+    /// ```
+    /// use jsnom::Number;
+    ///
+    /// assert_eq!(Number::from(3u64).as_u64(), Some(3));
+    /// assert_eq!(Number::from(-3i64).as_u64(), None);
+    /// ```
+    pub fn as_u64(&self) -> Option<u64> {
+        match self.n {
+            N::PosInt(n) => Some(n),
+            N::NegInt(_) | N::Float(_) => None,
+        }
+    }
+
+    /// Returns the number as an `f64`. Integers beyond 2^53 in magnitude may be rounded.
+    pub fn as_f64(&self) -> f64 {
+        match self.n {
+            N::PosInt(n) => n as f64,
+            N::NegInt(n) => n as f64,
+            N::Float(n) => n,
+        }
+    }
+}
+
+impl From<u64> for Number {
+    fn from(n: u64) -> Self {
+        Self {
+            n: N::PosInt(n),
+            literal: None,
+        }
+    }
+}
+
+impl From<i64> for Number {
+    fn from(n: i64) -> Self {
+        match u64::try_from(n) {
+            Ok(n) => n.into(),
+            Err(_) => Self {
+                n: N::NegInt(n),
+                literal: None,
+            },
+        }
+    }
+}
+
+impl From<f64> for Number {
+    fn from(n: f64) -> Self {
+        Self {
+            n: N::Float(n),
+            literal: None,
+        }
+    }
+}
+
+macro_rules! from_lossless {
+    ($($t:ty => $via:ty),*) => {
+        $(
+            impl From<$t> for Number {
+                fn from(n: $t) -> Self {
+                    <$via>::from(n).into()
+                }
+            }
+        )*
+    };
+}
+
+from_lossless!(u8 => u64, u16 => u64, u32 => u64, i8 => i64, i16 => i64, i32 => i64);
+
+/// Converts through the shortest decimal form of the `f32`, so that `0.1f32` becomes `0.1` rather
+/// than `0.10000000149011612`, the exact value of the nearest `f32`.
+///
+/// ```
+/// use jsnom::Number;
+///
+/// assert_eq!(Number::from(0.1f32).to_string(), "0.1");
+/// ```
+impl From<f32> for Number {
+    fn from(n: f32) -> Self {
+        if !n.is_finite() {
+            return f64::from(n).into();
+        }
+        format!("{n:e}").parse::<f64>().unwrap().into()
+    }
+}
+
+impl From<usize> for Number {
+    fn from(n: usize) -> Self {
+        (n as u64).into()
+    }
+}
+
+impl From<isize> for Number {
+    fn from(n: isize) -> Self {
+        (n as i64).into()
+    }
+}
+
+// The literal is only kept for writing the number back out, so it doesn't affect equality.
+impl PartialEq for Number {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Number {}
+
+impl Hash for Number {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        match self.n {
+            N::PosInt(n) => (0u8, n).hash(state),
+            N::NegInt(n) => (1u8, n).hash(state),
+            // Whole floats equal the integers with the same value, `-0.0` included, so they must
+            // hash the same.
+            N::Float(n) if cmp_u64_f64(n as u64, n) == Ordering::Equal => {
+                (0u8, n as u64).hash(state)
+            }
+            N::Float(n) if cmp_i64_f64(n as i64, n) == Ordering::Equal => {
+                (1u8, n as i64).hash(state)
+            }
+            N::Float(n) => (2u8, float_key(n).to_bits()).hash(state),
+        }
+    }
+}
+
+/// Numbers are ordered by value, whether they are integers or floats, so that the order agrees
+/// with [`Eq`]. `-0.0` and `0.0` are equal, and `NaN` comes after every other number.
+///
+/// ```
+/// use jsnom::Number;
+///
+/// let mut numbers = [Number::from(2.5), Number::from(-3i64), Number::from(2u64), Number::from(1.0)];
+/// numbers.sort();
+/// assert_eq!(numbers.map(|n| n.to_string()), ["-3", "1", "2", "2.5"]);
+/// assert_eq!(Number::from(2u64).cmp(&Number::from(2.0)), std::cmp::Ordering::Equal);
+/// ```
+impl Ord for Number {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self.n, other.n) {
+            (N::PosInt(a), N::PosInt(b)) => a.cmp(&b),
+            (N::NegInt(a), N::NegInt(b)) => a.cmp(&b),
+            (N::NegInt(_), N::PosInt(_)) => Ordering::Less,
+            (N::PosInt(_), N::NegInt(_)) => Ordering::Greater,
+            (N::Float(a), N::Float(b)) => float_key(a).total_cmp(&float_key(b)),
+            (N::PosInt(a), N::Float(b)) => cmp_u64_f64(a, b),
+            (N::NegInt(a), N::Float(b)) => cmp_i64_f64(a, b),
+            (N::Float(_), _) => other.cmp(self).reverse(),
+        }
+    }
+}
+
+impl PartialOrd for Number {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+// Makes `-0.0` and `0.0` the same, and puts every `NaN` after positive infinity.
+fn float_key(n: f64) -> f64 {
+    if n == 0.0 {
+        0.0
+    } else if n.is_nan() {
+        f64::NAN
+    } else {
+        n
+    }
+}
+
+// Compares exactly, rather than converting the integer to an `f64` and losing precision.
+fn cmp_u64_f64(a: u64, b: f64) -> Ordering {
+    if b.is_nan() || b >= 18446744073709551616.0 {
+        Ordering::Less
+    } else if b < 0.0 {
+        Ordering::Greater
+    } else {
+        // `b` truncates towards zero, so a fractional part makes it the larger.
+        let trunc = b as u64;
+        let fract = if trunc as f64 == b {
+            Ordering::Equal
+        } else {
+            Ordering::Less
+        };
+        a.cmp(&trunc).then(fract)
+    }
+}
+
+fn cmp_i64_f64(a: i64, b: f64) -> Ordering {
+    if b.is_nan() || b >= 0.0 {
+        Ordering::Less
+    } else if b < -9223372036854775808.0 {
+        Ordering::Greater
+    } else {
+        let trunc = b as i64;
+        let fract = if trunc as f64 == b {
+            Ordering::Equal
+        } else {
+            Ordering::Greater
+        };
+        a.cmp(&trunc).then(fract)
+    }
+}
+
+impl fmt::Display for Number {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if let Some(literal) = &self.literal {
+            return f.write_str(literal);
+        }
+        match self.n {
+            N::PosInt(n) => write!(f, "{n}"),
+            N::NegInt(n) => write!(f, "{n}"),
+            // `Debug` gives the shortest digits that parse back to the same `f64`, switching to
+            // an exponent for very large or small numbers where `Display` writes out every zero.
+            // Whole numbers are written without the `.0` it adds, as they compare equal to the
+            // integer they parse back as.
+            N::Float(n) => {
+                let s = format!("{n:?}");
+                f.write_str(s.strip_suffix(".0").unwrap_or(&s))
+            }
+        }
+    }
+}
+
+impl fmt::Debug for Number {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Number({self})")
+    }
+}
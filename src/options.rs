@@ -0,0 +1,105 @@
+/// Options controlling how lenient the parser is. The [`Default`] options follow the JSON spec.
+///
+/// ```
+/// use jsnom::ParseOptions;
+///
+/// let opts = ParseOptions {
+///     allow_trailing_commas: true,
+///     ..ParseOptions::default()
+/// };
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseOptions {
+    /// Accept a single trailing comma after the last element of an array or object, such as
+    /// `[1, 2,]`.
+    pub allow_trailing_commas: bool,
+    /// The deepest that arrays and objects may be nested before parsing fails, so that untrusted
+    /// input can't overflow the stack. Defaults to 128.
+    pub max_depth: usize,
+    /// Fail when a key appears more than once in the same object, such as `{"a": 1, "a": 2}`,
+    /// rather than keeping both entries. The error points at the repeated key.
+    pub reject_duplicate_keys: bool,
+    /// Keep only the last value for a key that appears more than once in the same object, at the
+    /// position where the key first appeared, as JavaScript's `JSON.parse` does. Has no effect if
+    /// `reject_duplicate_keys` is set.
+    pub dedup_keys: bool,
+    /// Treat `// line` and `/* block */` comments as whitespace between tokens, as in JSONC
+    /// files such as `tsconfig.json`.
+    pub allow_comments: bool,
+    /// Also accept strings and object keys between single quotes, such as `{'a': 'b'}`. The same
+    /// escapes are recognised in them, along with `\'` for a single quote.
+    pub allow_single_quotes: bool,
+    /// Also accept object keys written as bare identifiers, such as `{ name: "x" }`. An
+    /// identifier starts with a letter, `_` or `$`, followed by any of those or digits.
+    pub allow_unquoted_keys: bool,
+    /// Discard any lines at the start of the input that begin with `#` or `//`, such as a shebang
+    /// or a header added by a tool, along with blank lines between them. Unlike
+    /// `allow_comments`, this accepts no comments once the value has started.
+    pub skip_leading_comment_lines: bool,
+    /// Keep the text of each number as it was written, so that it is reproduced exactly when the
+    /// value is written back out. See [`Number::literal`](crate::Number::literal).
+    pub preserve_number_literals: bool,
+    /// The longest input, in bytes, that will be parsed at all. Longer input fails straight away
+    /// without being looked at. Defaults to `None`, for no limit.
+    pub max_length: Option<usize>,
+    /// The most values that may be parsed, counting every array, object, string, number, boolean
+    /// and null, however deeply nested. Parsing fails as soon as one more is found, which bounds
+    /// the memory a flat array of many small values can take. Defaults to `None`, for no limit.
+    pub max_nodes: Option<usize>,
+}
+
+impl Default for ParseOptions {
+    fn default() -> Self {
+        Self {
+            allow_trailing_commas: false,
+            max_depth: 128,
+            reject_duplicate_keys: false,
+            dedup_keys: false,
+            allow_comments: false,
+            allow_single_quotes: false,
+            allow_unquoted_keys: false,
+            skip_leading_comment_lines: false,
+            preserve_number_literals: false,
+            max_length: None,
+            max_nodes: None,
+        }
+    }
+}
+
+impl ParseOptions {
+    /// The options for plain JSON as specified by
+    /// [RFC 8259](https://datatracker.ietf.org/doc/html/rfc8259), which are the same as the
+    /// [`Default`] ones.
+    pub fn strict() -> Self {
+        Self::default()
+    }
+
+    /// Lenient options accepting most of [JSON5](https://json5.org/): comments, trailing commas,
+    /// single-quoted strings and unquoted object keys. As in JavaScript, a repeated key keeps
+    /// its last value. JSON5's hexadecimal and other extended number forms are not accepted.
+    ///
+    /// ```
+    /// use jsnom::{json, parse_with, ParseOptions};
+    ///
+    /// let config = "{
+    ///     // Where to listen.
+    ///     host: 'localhost',
+    ///     ports: [8080, 8081,],
+    /// }";
+    /// assert_eq!(
+    ///     parse_with(config, &ParseOptions::json5()),
+    ///     Ok(json!({"host": "localhost", "ports": [8080, 8081]}))
+    /// );
+    /// assert!(parse_with(config, &ParseOptions::strict()).is_err());
+    /// ```
+    pub fn json5() -> Self {
+        Self {
+            allow_trailing_commas: true,
+            dedup_keys: true,
+            allow_comments: true,
+            allow_single_quotes: true,
+            allow_unquoted_keys: true,
+            ..Self::default()
+        }
+    }
+}
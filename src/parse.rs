@@ -1,141 +1,871 @@
+use alloc::{
+    borrow::Cow,
+    collections::BTreeSet,
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::cell::{Cell, RefCell};
+
 use nom::{
     branch::alt,
-    bytes::complete::{tag, tag_no_case, take},
-    character::complete::{char, digit0, digit1, multispace0, none_of, one_of},
-    combinator::{map, opt, value},
-    error::{ParseError, VerboseError},
-    multi::{many0, separated_list0},
+    bytes::complete::{tag, tag_no_case, take_till, take_until, take_while, take_while_m_n},
+    character::complete::{char, digit0, digit1, multispace0, multispace1, one_of, satisfy},
+    combinator::{consumed, cut, map, map_opt, not, opt, recognize, value},
+    error::{context, ContextError, ErrorKind, ParseError, VerboseError},
+    multi::many0_count,
     sequence::{delimited, pair, preceded, terminated, tuple},
-    IResult,
+    IResult, Offset,
 };
 
-use crate::JsonValue;
+use crate::{JsonValue, Map, Number, ParseOptions, Spanned, SpannedValue};
 
 // whitespace delimited combinator from nom docs
-fn ws<'a, F: 'a, O, E: ParseError<&'a str>>(
-    inner: F,
-) -> impl FnMut(&'a str) -> IResult<&'a str, O, E>
+fn ws<'a, F, O, E: ParseError<&'a str>>(inner: F) -> impl FnMut(&'a str) -> IResult<&'a str, O, E>
 where
-    F: Fn(&'a str) -> IResult<&'a str, O, E>,
+    F: 'a + Fn(&'a str) -> IResult<&'a str, O, E>,
 {
     delimited(multispace0, inner, multispace0)
 }
 
+/// Parsers for the recursive parts of the grammar, which need to know the [`ParseOptions`] in use.
+pub(crate) struct Context<'o> {
+    opts: &'o ParseOptions,
+    depth: Cell<usize>,
+    nodes: Cell<usize>,
+    // The elements of every array being parsed, innermost last. Each array takes its own elements
+    // off the end once it is complete, so it is allocated once at its final size rather than
+    // grown as it goes.
+    elements: RefCell<Vec<JsonValue>>,
+    // Each key that has been repeated within an object, once, in the order first repeated. Only
+    // gathered with `dedup_keys`.
+    duplicates: RefCell<Vec<String>>,
+}
+
+impl<'o> Context<'o> {
+    pub(crate) fn new(opts: &'o ParseOptions) -> Self {
+        Self {
+            opts,
+            depth: Cell::new(0),
+            nodes: Cell::new(0),
+            elements: RefCell::new(Vec::new()),
+            duplicates: RefCell::new(Vec::new()),
+        }
+    }
+
+    /// Fails if the whole input is longer than the maximum length.
+    pub(crate) fn check_length<'a>(
+        &self,
+        s: &'a str,
+    ) -> Result<(), nom::Err<VerboseError<&'a str>>> {
+        match self.opts.max_length {
+            Some(max) if s.len() > max => Err(limit_exceeded(s, "maximum input length exceeded")),
+            _ => Ok(()),
+        }
+    }
+
+    // Skips lines starting with `#` or `//` at the start of the input, and blank lines between
+    // them, if that is allowed.
+    pub(crate) fn header<'a>(&self, mut s: &'a str) -> &'a str {
+        if !self.opts.skip_leading_comment_lines {
+            return s;
+        }
+        loop {
+            let line = s.trim_start_matches([' ', '\t', '\r']);
+            if let Some(rest) = line.strip_prefix('\n') {
+                s = rest;
+            } else if line.starts_with('#') || line.starts_with("//") {
+                s = line.find('\n').map_or("", |end| &line[end + 1..]);
+            } else {
+                return s;
+            }
+        }
+    }
+
+    // Counts a value starting at `s`, failing if that goes past the maximum number of values.
+    fn count<'a>(&self, s: &'a str) -> Result<(), nom::Err<VerboseError<&'a str>>> {
+        let nodes = self.nodes.get() + 1;
+        if self.opts.max_nodes.is_some_and(|max| nodes > max) {
+            let s = self.skip(s).map_or(s, |(rest, _)| rest);
+            return Err(limit_exceeded(s, "maximum number of values exceeded"));
+        }
+        self.nodes.set(nodes);
+        Ok(())
+    }
+
+    pub(crate) fn value<'a>(
+        &self,
+        s: &'a str,
+    ) -> IResult<&'a str, JsonValue, VerboseError<&'a str>> {
+        self.ws(s, |s| {
+            let (rest, value) = alt((
+                nom_null,
+                nom_bool,
+                |s| self.string(s),
+                |s| self.array(s),
+                |s| self.number(s),
+                |s| self.object(s),
+            ))(s)?;
+            self.count(s)?;
+            Ok((rest, value))
+        })
+    }
+
+    // An object between whitespace, counted like any other value, for documents that must be one.
+    pub(crate) fn root_object<'a>(
+        &self,
+        s: &'a str,
+    ) -> IResult<&'a str, JsonValue, VerboseError<&'a str>> {
+        self.ws(s, |s| {
+            let (rest, value) = self.object(s)?;
+            self.count(s)?;
+            Ok((rest, value))
+        })
+    }
+
+    pub(crate) fn array<'a>(
+        &self,
+        s: &'a str,
+    ) -> IResult<&'a str, JsonValue, VerboseError<&'a str>> {
+        // Once the opening bracket is seen this can only be an array, so any failure after it is
+        // reported from here rather than backtracking into the other alternatives.
+        context(
+            "array",
+            map(
+                preceded(
+                    |s| self.ws(s, char('[')),
+                    |s| {
+                        self.nested(
+                            s,
+                            cut(terminated(|s| self.elements(s), |s| self.ws(s, char(']')))),
+                        )
+                    },
+                ),
+                JsonValue::Array,
+            ),
+        )(s)
+    }
+
+    pub(crate) fn object<'a>(
+        &self,
+        s: &'a str,
+    ) -> IResult<&'a str, JsonValue, VerboseError<&'a str>> {
+        let inner = |s| {
+            let mut seen = BTreeSet::new();
+            let entry = |s| {
+                let (rest, key) = context("object key", |s| self.ws(s, |s| self.key(s)))(s)?;
+                self.unique_key(s, &key, &mut seen)?;
+                let (rest, value) = cut(preceded(
+                    char(':'),
+                    context("object value", |s| self.value(s)),
+                ))(rest)?;
+                Ok((rest, (key.into_owned(), value)))
+            };
+            self.comma_separated(s, entry)
+        };
+        let inner = preceded(
+            |s| self.ws(s, char('{')),
+            move |s| self.nested(s, cut(terminated(inner, |s| self.ws(s, char('}'))))),
+        );
+        context("object", map(inner, |entries| self.build_object(entries)))(s)
+    }
+
+    // The elements of an array, collected on the shared stack of elements.
+    fn elements<'a>(&self, s: &'a str) -> IResult<&'a str, Vec<JsonValue>, VerboseError<&'a str>> {
+        let start = self.elements.borrow().len();
+        let res = self.comma_separated_with(
+            s,
+            |s| context("array element", |s| self.value(s))(s),
+            |value| self.elements.borrow_mut().push(value),
+        );
+        let mut elements = self.elements.borrow_mut();
+        match res {
+            Ok((rest, ())) => Ok((rest, elements.split_off(start))),
+            Err(e) => {
+                elements.truncate(start);
+                Err(e)
+            }
+        }
+    }
+
+    /// Parses the same grammar as [`Context::value`], recording where in `input` each value and
+    /// object key was found. `s` must be a suffix of `input`.
+    pub(crate) fn spanned<'a>(
+        &self,
+        input: &'a str,
+        s: &'a str,
+    ) -> IResult<&'a str, Spanned<SpannedValue>, VerboseError<&'a str>> {
+        let scalar = map(
+            alt((nom_null, nom_bool, |s| self.string(s), |s| self.number(s))),
+            |value| match value {
+                JsonValue::Null => SpannedValue::Null,
+                JsonValue::Bool(b) => SpannedValue::Bool(b),
+                JsonValue::String(s) => SpannedValue::String(s),
+                JsonValue::Number(n) => SpannedValue::Number(n),
+                _ => unreachable!(),
+            },
+        );
+        let array = |s| {
+            let inner = preceded(
+                |s| self.ws(s, char('[')),
+                |s| {
+                    self.nested(
+                        s,
+                        cut(terminated(
+                            |s| {
+                                self.comma_separated(s, |s| {
+                                    context("array element", |s| self.spanned(input, s))(s)
+                                })
+                            },
+                            |s| self.ws(s, char(']')),
+                        )),
+                    )
+                },
+            );
+            context("array", map(inner, SpannedValue::Array))(s)
+        };
+        let object = |s| {
+            let inner = |s| {
+                let mut seen = BTreeSet::new();
+                let entry = |s| {
+                    let (rest, (text, key)) =
+                        context("object key", consumed(|s| self.ws(s, |s| self.key(s))))(s)?;
+                    self.unique_key(s, &key, &mut seen)?;
+                    let (rest, value) = cut(preceded(
+                        char(':'),
+                        context("object value", |s| self.spanned(input, s)),
+                    ))(rest)?;
+                    Ok((rest, (span(input, text, key.into_owned()), value)))
+                };
+                self.comma_separated(s, entry)
+            };
+            let inner = preceded(
+                |s| self.ws(s, char('{')),
+                move |s| self.nested(s, cut(terminated(inner, |s| self.ws(s, char('}'))))),
+            );
+            context("object", map(inner, SpannedValue::Object))(s)
+        };
+        let mut any = consumed(alt((scalar, array, object)));
+        self.ws(s, |s| {
+            let (rest, (text, value)) = any(s)?;
+            self.count(s)?;
+            Ok((rest, span(input, text, value)))
+        })
+    }
+
+    // A string, which may also be single-quoted if that is allowed.
+    fn str<'a>(&self, s: &'a str) -> IResult<&'a str, Cow<'a, str>, VerboseError<&'a str>> {
+        if self.opts.allow_single_quotes {
+            alt((nom_str, nom_quoted('\'')))(s)
+        } else {
+            nom_str(s)
+        }
+    }
+
+    // An object key, which may also be a bare identifier such as `name` if that is allowed.
+    fn key<'a>(&self, s: &'a str) -> IResult<&'a str, Cow<'a, str>, VerboseError<&'a str>> {
+        if self.opts.allow_unquoted_keys {
+            alt((|s| self.str(s), map(nom_identifier, Cow::Borrowed)))(s)
+        } else {
+            self.str(s)
+        }
+    }
+
+    fn string<'a>(&self, s: &'a str) -> IResult<&'a str, JsonValue, VerboseError<&'a str>> {
+        map(|s| self.str(s), |s| JsonValue::String(s.into_owned()))(s)
+    }
+
+    fn number<'a>(&self, s: &'a str) -> IResult<&'a str, JsonValue, VerboseError<&'a str>> {
+        let (rest, number) = nom_number(s)?;
+        match number {
+            JsonValue::Number(n) if self.opts.preserve_number_literals => {
+                let literal = s[..s.len() - rest.len()].trim_matches([' ', '\t', '\n', '\r']);
+                Ok((rest, JsonValue::Number(n.with_literal(literal))))
+            }
+            number => Ok((rest, number)),
+        }
+    }
+
+    // Runs `inner` between whitespace, which includes comments if they are allowed.
+    fn ws<'a, O, F>(&self, s: &'a str, mut inner: F) -> IResult<&'a str, O, VerboseError<&'a str>>
+    where
+        F: FnMut(&'a str) -> IResult<&'a str, O, VerboseError<&'a str>>,
+    {
+        let (s, _) = self.skip(s)?;
+        let (s, o) = inner(s)?;
+        let (s, _) = self.skip(s)?;
+        Ok((s, o))
+    }
+
+    fn skip<'a>(&self, s: &'a str) -> IResult<&'a str, (), VerboseError<&'a str>> {
+        if self.opts.allow_comments {
+            value((), many0_count(alt((multispace1, nom_comment))))(s)
+        } else {
+            value((), multispace0)(s)
+        }
+    }
+
+    // Runs `inner` one level deeper, failing instead if that goes past the maximum depth.
+    fn nested<'a, O, F>(
+        &self,
+        s: &'a str,
+        mut inner: F,
+    ) -> IResult<&'a str, O, VerboseError<&'a str>>
+    where
+        F: FnMut(&'a str) -> IResult<&'a str, O, VerboseError<&'a str>>,
+    {
+        let depth = self.depth.get() + 1;
+        if depth > self.opts.max_depth {
+            return Err(limit_exceeded(s, "maximum nesting depth exceeded"));
+        }
+        self.depth.set(depth);
+        let res = inner(s);
+        self.depth.set(depth - 1);
+        res
+    }
+
+    /// Parses the same grammar as [`Context::value`], but keeps the arrays and objects being built
+    /// on a heap-allocated stack instead of recursing into them, so that nesting is bounded by
+    /// memory rather than the call stack. The maximum depth is not enforced.
+    pub(crate) fn deep_value<'a>(
+        &self,
+        s: &'a str,
+    ) -> IResult<&'a str, JsonValue, VerboseError<&'a str>> {
+        let mut builder = Builder {
+            cx: self,
+            stack: Vec::new(),
+            value: None,
+        };
+        let (rest, ()) = self.events(s, |event| builder.event(event))?;
+        Ok((rest, builder.value.unwrap()))
+    }
+
+    /// Parses the same grammar as [`Context::value`], passing each part of it to `emit` as it is
+    /// seen instead of building a [`JsonValue`]. Like [`Context::deep_value`], this doesn't
+    /// recurse into arrays and objects.
+    pub(crate) fn events<'a>(
+        &self,
+        mut s: &'a str,
+        mut emit: impl FnMut(Event<'a>),
+    ) -> IResult<&'a str, (), VerboseError<&'a str>> {
+        let token = |c| move |s| self.ws(s, char(c));
+        let mut stack = Vec::new();
+        'value: loop {
+            // Open any arrays and objects starting here, until a complete value is found.
+            if let Ok((rest, _)) = token('[')(s) {
+                self.count(s)?;
+                emit(Event::BeginArray);
+                match token(']')(rest) {
+                    Ok((rest, _)) => {
+                        emit(Event::EndArray);
+                        s = rest;
+                    }
+                    Err(_) => {
+                        stack.push(Frame::Array);
+                        s = rest;
+                        continue;
+                    }
+                }
+            } else if let Ok((rest, _)) = token('{')(s) {
+                self.count(s)?;
+                emit(Event::BeginObject);
+                match token('}')(rest) {
+                    Ok((rest, _)) => {
+                        emit(Event::EndObject);
+                        s = rest;
+                    }
+                    Err(_) => {
+                        let (after_key, key) = self.object_key(rest)?;
+                        let mut seen = BTreeSet::new();
+                        self.unique_key(rest, &key, &mut seen)?;
+                        emit(Event::Key(key));
+                        stack.push(Frame::Object(seen));
+                        s = after_key;
+                        continue;
+                    }
+                }
+            } else {
+                let leaf = alt((
+                    map(|s| self.str(s), Event::String),
+                    map(
+                        alt((nom_null, nom_bool, |s| self.number(s))),
+                        |value| match value {
+                            JsonValue::Null => Event::Null,
+                            JsonValue::Bool(b) => Event::Bool(b),
+                            JsonValue::Number(n) => Event::Number(n),
+                            _ => unreachable!(),
+                        },
+                    ),
+                ));
+                let (rest, event) = self.ws(s, leaf).map_err(|e| match e {
+                    // Inside an array or object there is nothing to backtrack to.
+                    nom::Err::Error(e) if !stack.is_empty() => nom::Err::Failure(e),
+                    e => e,
+                })?;
+                self.count(s)?;
+                emit(event);
+                s = rest;
+            }
+
+            // Close every array and object that this value completes.
+            loop {
+                let Some(frame) = stack.last_mut() else {
+                    return Ok((s, ()));
+                };
+                let close = frame.close();
+                match char::<_, VerboseError<&str>>(',')(s) {
+                    Ok((rest, _))
+                        if !(self.opts.allow_trailing_commas && token(close)(rest).is_ok()) =>
+                    {
+                        s = match frame {
+                            Frame::Array => rest,
+                            Frame::Object(seen) => {
+                                let (after_key, key) = self.object_key(rest)?;
+                                self.unique_key(rest, &key, seen)?;
+                                emit(Event::Key(key));
+                                after_key
+                            }
+                        };
+                        continue 'value;
+                    }
+                    Ok((rest, _)) => s = rest,
+                    Err(_) => {}
+                }
+                (s, _) = cut(token(close))(s)?;
+                match stack.pop().unwrap() {
+                    Frame::Array => emit(Event::EndArray),
+                    Frame::Object(_) => emit(Event::EndObject),
+                }
+            }
+        }
+    }
+
+    // Without `dedup_keys`, repeated keys are left for `Map` to deal with.
+    fn build_object(&self, entries: Vec<(String, JsonValue)>) -> JsonValue {
+        if !self.opts.dedup_keys {
+            return JsonValue::Object(Map::from(entries));
+        }
+        let mut map = Map::with_capacity(entries.len());
+        for (key, value) in entries {
+            if map.get(&key).is_some() {
+                let mut duplicates = self.duplicates.borrow_mut();
+                if !duplicates.contains(&key) {
+                    duplicates.push(key.clone());
+                }
+            }
+            map.insert(key, value);
+        }
+        JsonValue::Object(map)
+    }
+
+    // Takes the keys that were repeated within objects since this was last called.
+    pub(crate) fn take_duplicates(&self) -> Vec<String> {
+        self.duplicates.take()
+    }
+
+    // An object key along with the `:` after it, once the object has been opened.
+    fn object_key<'a>(&self, s: &'a str) -> IResult<&'a str, Cow<'a, str>, VerboseError<&'a str>> {
+        cut(terminated(
+            context("object key", |s| self.ws(s, |s| self.key(s))),
+            char(':'),
+        ))(s)
+    }
+
+    // Records `key`, parsed from the start of `s`, failing there if it was already seen in the same
+    // object and duplicates are rejected.
+    fn unique_key<'a>(
+        &self,
+        s: &'a str,
+        key: &str,
+        seen: &mut BTreeSet<String>,
+    ) -> Result<(), nom::Err<VerboseError<&'a str>>> {
+        if self.opts.reject_duplicate_keys && !seen.insert(key.to_string()) {
+            let s = self.skip(s).map_or(s, |(rest, _)| rest);
+            return Err(nom::Err::Failure(VerboseError::add_context(
+                s,
+                "duplicate object key",
+                VerboseError::from_error_kind(s, ErrorKind::Verify),
+            )));
+        }
+        Ok(())
+    }
+
+    fn comma_separated<'a, O, F>(
+        &self,
+        s: &'a str,
+        item: F,
+    ) -> IResult<&'a str, Vec<O>, VerboseError<&'a str>>
+    where
+        F: FnMut(&'a str) -> IResult<&'a str, O, VerboseError<&'a str>>,
+    {
+        let mut items = Vec::new();
+        let (s, ()) = self.comma_separated_with(s, item, |o| items.push(o))?;
+        Ok((s, items))
+    }
+
+    // Passes each item to `push` as it is parsed. An item is required after every comma, so that
+    // the error points at the bad item rather than at the comma before it. A single trailing
+    // comma is only accepted after at least one item, so `[,]` is an error even when trailing
+    // commas are allowed.
+    fn comma_separated_with<'a, O, F>(
+        &self,
+        s: &'a str,
+        mut item: F,
+        mut push: impl FnMut(O),
+    ) -> IResult<&'a str, (), VerboseError<&'a str>>
+    where
+        F: FnMut(&'a str) -> IResult<&'a str, O, VerboseError<&'a str>>,
+    {
+        let (mut s, first) = opt(&mut item)(s)?;
+        let Some(first) = first else {
+            return Ok((s, ()));
+        };
+        push(first);
+
+        while let Ok((after_comma, _)) = char::<_, VerboseError<&str>>(',')(s) {
+            match item(after_comma) {
+                Ok((rest, o)) => {
+                    push(o);
+                    s = rest;
+                }
+                Err(nom::Err::Error(_)) if self.opts.allow_trailing_commas => {
+                    return Ok((after_comma, ()));
+                }
+                Err(nom::Err::Error(e)) => return Err(nom::Err::Failure(e)),
+                Err(e) => return Err(e),
+            }
+        }
+        Ok((s, ()))
+    }
+}
+
+// An array or object that `Context::events` is part way through. An object also holds the keys
+// seen so far if duplicates are rejected.
+enum Frame {
+    Array,
+    Object(BTreeSet<String>),
+}
+
+impl Frame {
+    fn close(&self) -> char {
+        match self {
+            Self::Array => ']',
+            Self::Object(_) => '}',
+        }
+    }
+}
+
+/// A part of a JSON document, as reported by [`Context::events`]. Strings borrow from the input
+/// where they have no escapes.
+pub(crate) enum Event<'a> {
+    Null,
+    Bool(bool),
+    String(Cow<'a, str>),
+    Number(Number),
+    BeginArray,
+    EndArray,
+    BeginObject,
+    Key(Cow<'a, str>),
+    EndObject,
+}
+
+// Builds a `JsonValue` from the events of `Context::events`, for `Context::deep_value`.
+struct Builder<'c, 'o> {
+    cx: &'c Context<'o>,
+    stack: Vec<Partial>,
+    value: Option<JsonValue>,
+}
+
+// An array or object that a `Builder` is part way through, along with the key for the next value
+// of an object.
+enum Partial {
+    Array(Vec<JsonValue>),
+    Object(Vec<(String, JsonValue)>, String),
+}
+
+impl Builder<'_, '_> {
+    fn event(&mut self, event: Event) {
+        let value = match event {
+            Event::Null => JsonValue::Null,
+            Event::Bool(b) => JsonValue::Bool(b),
+            Event::String(s) => JsonValue::String(s.into_owned()),
+            Event::Number(n) => JsonValue::Number(n),
+            Event::BeginArray => return self.stack.push(Partial::Array(Vec::new())),
+            Event::BeginObject => {
+                return self.stack.push(Partial::Object(Vec::new(), String::new()));
+            }
+            Event::Key(key) => {
+                if let Some(Partial::Object(_, next)) = self.stack.last_mut() {
+                    *next = key.into_owned();
+                }
+                return;
+            }
+            Event::EndArray | Event::EndObject => match self.stack.pop().unwrap() {
+                Partial::Array(items) => JsonValue::Array(items),
+                Partial::Object(entries, _) => self.cx.build_object(entries),
+            },
+        };
+        match self.stack.last_mut() {
+            None => self.value = Some(value),
+            Some(Partial::Array(items)) => items.push(value),
+            Some(Partial::Object(entries, key)) => entries.push((core::mem::take(key), value)),
+        }
+    }
+}
+
+// Locates `value`, parsed from `text`, within `input`. The parsers skip whitespace on either side,
+// which isn't part of the span.
+fn span<T>(input: &str, text: &str, value: T) -> Spanned<T> {
+    let text = text.trim_matches([' ', '\t', '\n', '\r']);
+    let start = input.offset(text);
+    Spanned {
+        value,
+        start,
+        end: start + text.len(),
+    }
+}
+
+// The failure for input that goes past one of the limits in `ParseOptions`.
+fn limit_exceeded<'a>(s: &'a str, label: &'static str) -> nom::Err<VerboseError<&'a str>> {
+    nom::Err::Failure(VerboseError::add_context(
+        s,
+        label,
+        VerboseError::from_error_kind(s, ErrorKind::TooLarge),
+    ))
+}
+
 pub(crate) fn nom_parse(s: &str) -> IResult<&str, JsonValue, VerboseError<&str>> {
-    alt((
-        nom_null, nom_bool, nom_string, nom_array, nom_number, nom_object,
-    ))(s)
+    Context::new(&ParseOptions::default()).value(s)
 }
 
 pub(crate) fn nom_null(s: &str) -> IResult<&str, JsonValue, VerboseError<&str>> {
-    map(ws(tag("null")), |_| JsonValue::Null)(s)
+    ws(nom_null_exact)(s)
+}
+
+// `null`, without any whitespace around it.
+pub(crate) fn nom_null_exact(s: &str) -> IResult<&str, JsonValue, VerboseError<&str>> {
+    value(JsonValue::Null, tag("null"))(s)
 }
 
 pub(crate) fn nom_bool(s: &str) -> IResult<&str, JsonValue, VerboseError<&str>> {
-    match alt((ws(tag("true")), ws(tag("false"))))(s) {
-        Ok((rest, "true")) => Ok((rest, JsonValue::Bool(true))),
-        Ok((rest, "false")) => Ok((rest, JsonValue::Bool(false))),
-        Err(e) => Err(e),
-        _ => unreachable!(),
-    }
+    ws(nom_bool_exact)(s)
+}
+
+pub(crate) fn nom_bool_exact(s: &str) -> IResult<&str, JsonValue, VerboseError<&str>> {
+    alt((
+        value(JsonValue::Bool(true), tag("true")),
+        value(JsonValue::Bool(false), tag("false")),
+    ))(s)
+}
+
+// A `// line` or `/* block */` comment. Block comments don't nest.
+fn nom_comment(s: &str) -> IResult<&str, &str, VerboseError<&str>> {
+    alt((
+        recognize(pair(tag("//"), take_till(|c| c == '\n'))),
+        recognize(preceded(
+            tag("/*"),
+            cut(context("comment", terminated(take_until("*/"), tag("*/")))),
+        )),
+    ))(s)
 }
 
 fn nom_escaped_char(s: &str) -> IResult<&str, char, VerboseError<&str>> {
     preceded(
         char('\\'),
-        alt((
-            value('\"', char('"')),
-            value('\\', char('\\')),
-            value('\u{0008}', char('b')),
-            value('\u{000c}', char('f')),
-            value('\n', char('n')),
-            value('\r', char('r')),
-            value('\t', char('t')),
-            value('\t', char('t')),
-            // unicode literals
-            map(tuple((char('u'), take(4usize))), |(_, code)| {
-                char::from_u32(u32::from_str_radix(code, 16).unwrap()).unwrap()
-            }),
+        // Anything after a backslash must be a valid escape, so don't let the caller backtrack
+        // into treating it as a literal character.
+        cut(context(
+            "escape",
+            alt((
+                value('\"', char('"')),
+                value('\\', char('\\')),
+                value('/', char('/')),
+                value('\u{0008}', char('b')),
+                value('\u{000c}', char('f')),
+                value('\n', char('n')),
+                value('\r', char('r')),
+                value('\t', char('t')),
+                nom_unicode_escape,
+            )),
         )),
     )(s)
 }
 
-pub(crate) fn nom_string(s: &str) -> IResult<&str, JsonValue, VerboseError<&str>> {
-    match delimited(
-        preceded(multispace0, char('"')),
-        map(many0(alt((nom_escaped_char, none_of("\"")))), |cs| {
-            cs.iter().collect::<String>()
-        }),
-        terminated(char('"'), multispace0),
+fn nom_hex_code(s: &str) -> IResult<&str, u32, VerboseError<&str>> {
+    map_opt(
+        preceded(
+            char('u'),
+            take_while_m_n(4, 4, |c: char| c.is_ascii_hexdigit()),
+        ),
+        |code| u32::from_str_radix(code, 16).ok(),
     )(s)
-    {
-        Ok((rest, string)) => Ok((rest, JsonValue::String(string))),
-        Err(e) => Err(e),
+}
+
+// Characters outside the BMP are written as a UTF-16 surrogate pair, e.g. `\uD83D\uDE00`. A lone
+// surrogate of either kind is not a valid `char` and is rejected.
+fn nom_unicode_escape(s: &str) -> IResult<&str, char, VerboseError<&str>> {
+    let (rest, high) = nom_hex_code(s)?;
+    if (0xd800..=0xdbff).contains(&high) {
+        map_opt(preceded(char('\\'), nom_hex_code), |low| {
+            if (0xdc00..=0xdfff).contains(&low) {
+                char::from_u32(0x10000 + ((high - 0xd800) << 10) + (low - 0xdc00))
+            } else {
+                None
+            }
+        })(rest)
+    } else {
+        match char::from_u32(high) {
+            Some(c) => Ok((rest, c)),
+            None => Err(nom::Err::Error(VerboseError::from_error_kind(
+                s,
+                ErrorKind::MapOpt,
+            ))),
+        }
+    }
+}
+
+// The contents of a string, up to the closing `quote`. Runs of characters between escapes are
+// copied across whole, and a string with no escapes at all is borrowed from the input. Control
+// characters and the quote itself have to be escaped, and `\'` is only an escape inside `'...'`.
+fn quoted_contents(
+    quote: char,
+) -> impl Fn(&str) -> IResult<&str, Cow<'_, str>, VerboseError<&str>> {
+    move |s| {
+        let is_plain_char = |c: char| c != quote && c != '\\' && c >= '\u{0020}';
+        let (mut s, run) = take_while(is_plain_char)(s)?;
+        if !s.starts_with('\\') {
+            return Ok((s, Cow::Borrowed(run)));
+        }
+        let mut contents = run.to_string();
+        while s.starts_with('\\') {
+            let (rest, c) = match s.strip_prefix("\\'") {
+                Some(rest) if quote == '\'' => (rest, '\''),
+                _ => nom_escaped_char(s)?,
+            };
+            let (rest, run) = take_while(is_plain_char)(rest)?;
+            contents.push(c);
+            contents.push_str(run);
+            s = rest;
+        }
+        Ok((s, Cow::Owned(contents)))
     }
 }
 
+pub(crate) fn nom_string_contents(s: &str) -> IResult<&str, Cow<'_, str>, VerboseError<&str>> {
+    quoted_contents('"')(s)
+}
+
+// A string between `quote`s, borrowed from the input if it has no escapes to decode.
+fn nom_quoted(quote: char) -> impl Fn(&str) -> IResult<&str, Cow<'_, str>, VerboseError<&str>> {
+    move |s| {
+        context(
+            "string",
+            preceded(
+                preceded(multispace0, char(quote)),
+                cut(terminated(
+                    quoted_contents(quote),
+                    terminated(char(quote), multispace0),
+                )),
+            ),
+        )(s)
+    }
+}
+
+// A letter, `_` or `$`, followed by any number of those or digits.
+fn nom_identifier(s: &str) -> IResult<&str, &str, VerboseError<&str>> {
+    let is_start = |c: char| c.is_alphabetic() || c == '_' || c == '$';
+    recognize(pair(
+        satisfy(is_start),
+        take_while(move |c: char| is_start(c) || c.is_alphanumeric()),
+    ))(s)
+}
+
+pub(crate) fn nom_str(s: &str) -> IResult<&str, Cow<'_, str>, VerboseError<&str>> {
+    nom_quoted('"')(s)
+}
+
+fn nom_string_literal(s: &str) -> IResult<&str, String, VerboseError<&str>> {
+    map(nom_str, Cow::into_owned)(s)
+}
+
+pub(crate) fn nom_string(s: &str) -> IResult<&str, JsonValue, VerboseError<&str>> {
+    map(nom_string_literal, JsonValue::String)(s)
+}
+
 pub(crate) fn nom_array(s: &str) -> IResult<&str, JsonValue, VerboseError<&str>> {
-    map(
-        delimited(
-            ws(char('[')),
-            terminated(separated_list0(char(','), nom_parse), opt(char(','))),
-            ws(char(']')),
-        ),
-        JsonValue::Array,
-    )(s)
+    Context::new(&ParseOptions::default()).array(s)
 }
 
 pub(crate) fn nom_number(s: &str) -> IResult<&str, JsonValue, VerboseError<&str>> {
-    // The JSON spec for numbers is pretty weird. You can have one leading 0 and then any number of
-    // digits. Second digit in the `integer` part cannot be a 0. Also, +/- sign is ok for exponent
-    // part, but the integer part can only have `-` or no sign.
-    let integer = tuple((
+    ws(nom_number_exact)(s)
+}
+
+pub(crate) fn nom_number_exact(s: &str) -> IResult<&str, JsonValue, VerboseError<&str>> {
+    // The JSON spec for numbers is pretty weird. The integer part is either a lone 0, or a
+    // nonzero digit followed by any digits, so leading zeros like `0234` are not allowed. Also, an
+    // optional +/- sign is ok for exponent part, but the integer part can only have `-` or no sign.
+    let integer = pair(
         opt(tag("-")),
-        one_of("1234567890"),
-        opt(tuple((one_of("123456789"), digit0))),
-    ));
-    let floating = preceded(char('.'), digit0);
-    let exponent = preceded(
-        tag_no_case("e"),
-        tuple((alt((char('+'), char('-'))), digit1)),
+        alt((
+            terminated(tag("0"), not(digit1)),
+            recognize(pair(one_of("123456789"), digit0)),
+        )),
     );
+    // Once a `.` or exponent marker is seen, digits must follow, so `1.` is an error rather than
+    // a `1` followed by leftover input.
+    let floating = preceded(char('.'), cut(digit1));
+    let exponent = preceded(tag_no_case("e"), cut(tuple((opt(one_of("+-")), digit1))));
 
-    let (rest, ((minus, first, other), floating, exponent)) = delimited(
-        multispace0,
-        tuple((integer, opt(floating), opt(exponent))),
-        multispace0,
+    // The literal is parsed directly from the matched input, which Rust's parsers accept as is.
+    // Parsing the exponent as part of it keeps the result correctly rounded, which multiplying by a
+    // power of ten afterwards does not.
+    let (rest, (literal, ((minus, _), floating, exponent))) = context(
+        "number",
+        consumed(tuple((integer, opt(floating), opt(exponent)))),
     )(s)?;
 
-    let mut number = minus.unwrap_or("").to_string() + &first.to_string();
-    if let Some((first, second)) = other {
-        number += &(first.to_string() + second);
-    }
-
-    if let Some(digits) = floating {
-        number += ".";
-        number += digits;
-    }
-
-    let mut number: f32 = number.parse().unwrap();
-
-    if let Some((sign, exponent)) = exponent {
-        let exponent = (sign.to_string() + exponent).parse().unwrap();
-        number *= 10f32.powf(exponent);
-    }
+    // Integers are kept exact where they fit, and otherwise fall back to a float. `-0` is a
+    // float too, since integers can't hold its sign.
+    let is_integer = floating.is_none() && exponent.is_none();
+    let integer = match minus {
+        _ if !is_integer => None,
+        Some(_) => literal
+            .parse::<i64>()
+            .ok()
+            .filter(|&n| n != 0)
+            .map(Number::from),
+        None => literal.parse::<u64>().ok().map(Number::from),
+    };
+    let number = match integer {
+        Some(integer) => integer,
+        None => {
+            // JSON has no infinities, so a number too large for an `f64` is an error rather than
+            // silently becoming one.
+            let float = literal.parse::<f64>().unwrap();
+            if float.is_infinite() {
+                let s = &s[s.offset(literal)..];
+                return Err(nom::Err::Failure(VerboseError::add_context(
+                    s,
+                    "number out of range",
+                    VerboseError::from_error_kind(s, ErrorKind::TooLarge),
+                )));
+            }
+            // Integers too large for a `u64` or `i64` keep their digits, for `Number::as_bigint`.
+            #[cfg(feature = "num-bigint")]
+            if is_integer && float != 0.0 {
+                return Ok((
+                    rest,
+                    JsonValue::Number(Number::from(float).with_literal(literal)),
+                ));
+            }
+            Number::from(float)
+        }
+    };
 
     Ok((rest, JsonValue::Number(number)))
 }
 
 pub(crate) fn nom_object(s: &str) -> IResult<&str, JsonValue, VerboseError<&str>> {
-    let inner = terminated(
-        separated_list0(
-            char(','),
-            pair(terminated(nom_string, char(':')), nom_parse),
-        ),
-        opt(char(',')),
-    );
-    let inner = delimited(ws(char('{')), inner, ws(char('}')));
-    map(inner, JsonValue::Object)(s)
+    Context::new(&ParseOptions::default()).object(s)
 }
 
 #[cfg(test)]
@@ -180,6 +910,37 @@ mod test {
         assert_eq!(super::nom_escaped_char("\\u0d9e"), Ok(("", '\u{0d9e}')))
     }
 
+    #[test]
+    fn nom_unicode_invalid_hex() {
+        assert!(super::nom_string("\"\\uZZZZ\"").is_err());
+    }
+
+    #[test]
+    fn nom_unicode_invalid_code_point() {
+        assert!(super::nom_string("\"\\ud800\"").is_err());
+    }
+
+    #[test]
+    fn nom_unicode_surrogate_pair() {
+        assert_eq!(super::nom_escaped_char("\\uD83D\\uDE00"), Ok(("", '😀')))
+    }
+
+    #[test]
+    fn nom_unicode_unpaired_high_surrogate() {
+        assert!(super::nom_string("\"\\uD83Dabc\"").is_err());
+    }
+
+    #[test]
+    fn nom_unicode_unpaired_low_surrogate() {
+        assert!(super::nom_string("\"\\uDE00\"").is_err());
+    }
+
+    #[test]
+    fn nom_string_control_char() {
+        assert!(super::nom_string("\"hello,\nworld!\"").is_err());
+        assert!(super::nom_string("\"\t\"").is_err());
+    }
+
     #[test]
     fn nom_string_escaped() {
         assert_eq!(
@@ -188,6 +949,24 @@ mod test {
         );
     }
 
+    #[test]
+    fn nom_string_all_escapes() {
+        assert_eq!(
+            super::nom_string("\"a\\/b\""),
+            Ok(("", JsonValue::String("a/b".to_string())))
+        );
+        assert_eq!(
+            super::nom_string("\"\\\"\\\\\\/\\b\\f\\n\\r\\t\\u0041\""),
+            Ok((
+                "",
+                JsonValue::String("\"\\/\u{0008}\u{000c}\n\r\tA".to_string())
+            ))
+        );
+        // Every escape written back out parses to the same string.
+        let s = JsonValue::String("\"\\/\u{0008}\u{000c}\n\r\t\u{0001}".to_string());
+        assert_eq!(super::nom_string(&s.to_string()), Ok(("", s)));
+    }
+
     #[test]
     fn nom_array() {
         use JsonValue::*;
@@ -212,28 +991,271 @@ mod test {
         );
     }
 
+    #[test]
+    fn nom_array_trailing_comma() {
+        assert!(super::nom_array("[null, null,]").is_err());
+    }
+
+    #[test]
+    fn nom_array_max_depth() {
+        let deep = "[".repeat(100_000) + &"]".repeat(100_000);
+        assert!(matches!(super::nom_array(&deep), Err(nom::Err::Failure(_))));
+
+        let ok = "[".repeat(128) + &"]".repeat(128);
+        assert!(super::nom_array(&ok).is_ok());
+        let too_deep = "[".repeat(129) + &"]".repeat(129);
+        assert!(super::nom_array(&too_deep).is_err());
+    }
+
+    #[test]
+    fn max_depth_option() {
+        use super::{Context, ParseOptions};
+        let opts = ParseOptions {
+            max_depth: 2,
+            ..ParseOptions::default()
+        };
+        assert!(Context::new(&opts).value("[{\"a\": 1}, []]").is_ok());
+        assert!(Context::new(&opts).value("[{\"a\": [1]}]").is_err());
+    }
+
+    #[test]
+    fn deep_value_matches_value() {
+        use super::{Context, ParseOptions};
+        let opts = ParseOptions::default();
+        for s in [
+            "null",
+            " [ ] ",
+            "{}",
+            "[1, [2, [3, []]], {\"a\": {\"b\": [true, \"c\"]}}, -0.5e3]",
+            " { \"a\" :[ {} , { \"\\u00e9\":null } ] , \"b\": \"\" } trailing",
+        ] {
+            assert_eq!(
+                Context::new(&opts).deep_value(s),
+                Context::new(&opts).value(s)
+            );
+        }
+    }
+
+    #[test]
+    fn deep_value_nesting() {
+        use super::{Context, ParseOptions};
+        let opts = ParseOptions::default();
+        let deep = "[{\"a\":".repeat(1000) + "null" + &"}]".repeat(1000);
+        assert!(Context::new(&opts).value(&deep).is_err());
+        let (rest, mut value) = Context::new(&opts).deep_value(&deep).unwrap();
+        assert_eq!(rest, "");
+        for _ in 0..1000 {
+            let JsonValue::Array(mut items) = value else {
+                panic!("expected an array");
+            };
+            let JsonValue::Object(mut entries) = items.remove(0) else {
+                panic!("expected an object");
+            };
+            value = entries.remove("a").unwrap();
+        }
+        assert_eq!(value, JsonValue::Null);
+    }
+
+    #[test]
+    fn deep_value_errors() {
+        use super::{Context, ParseOptions};
+        let opts = ParseOptions::default();
+        for s in [
+            "[1, ]",
+            "[1 2]",
+            "{\"a\" 1}",
+            "{\"a\": }",
+            "[[1]",
+            "{1: 2}",
+            "[nul]",
+        ] {
+            assert!(matches!(
+                Context::new(&opts).deep_value(s),
+                Err(nom::Err::Failure(_))
+            ));
+        }
+        assert!(matches!(
+            Context::new(&opts).deep_value("nul"),
+            Err(nom::Err::Error(_))
+        ));
+
+        let opts = ParseOptions {
+            allow_trailing_commas: true,
+            ..ParseOptions::default()
+        };
+        assert_eq!(
+            Context::new(&opts).deep_value("[[1,], {\"a\": 2,},]"),
+            Ok(("", json!([[1], {"a": 2}])))
+        );
+        assert!(Context::new(&opts).deep_value("[,]").is_err());
+    }
+
+    #[test]
+    fn events_order() {
+        use super::{Context, Number, ParseOptions};
+        use crate::JsonVisitor;
+
+        #[derive(Default)]
+        struct Log(Vec<String>);
+
+        impl JsonVisitor for Log {
+            fn on_null(&mut self) {
+                self.0.push("null".to_string());
+            }
+            fn on_bool(&mut self, b: bool) {
+                self.0.push(b.to_string());
+            }
+            fn on_string(&mut self, s: &str) {
+                self.0.push(format!("string {s}"));
+            }
+            fn on_number(&mut self, n: Number) {
+                self.0.push(format!("number {n}"));
+            }
+            fn begin_array(&mut self) {
+                self.0.push("[".to_string());
+            }
+            fn end_array(&mut self) {
+                self.0.push("]".to_string());
+            }
+            fn begin_object(&mut self) {
+                self.0.push("{".to_string());
+            }
+            fn on_key(&mut self, key: &str) {
+                self.0.push(format!("key {key}"));
+            }
+            fn end_object(&mut self) {
+                self.0.push("}".to_string());
+            }
+        }
+
+        let mut log = Log::default();
+        let s = "{\"a\": [null, true, \"x\"], \"b\": {}, \"c\": [[]], \"d\": -1.5}";
+        assert_eq!(
+            Context::new(&ParseOptions::default()).events(s, |event| event.visit(&mut log)),
+            Ok(("", ()))
+        );
+        assert_eq!(
+            log.0,
+            [
+                "{",
+                "key a",
+                "[",
+                "null",
+                "true",
+                "string x",
+                "]",
+                "key b",
+                "{",
+                "}",
+                "key c",
+                "[",
+                "[",
+                "]",
+                "]",
+                "key d",
+                "number -1.5",
+                "}"
+            ]
+        );
+    }
+
+    #[test]
+    fn nom_str_borrows() {
+        use std::borrow::Cow;
+        assert_eq!(
+            super::nom_str(" \"plain\" "),
+            Ok(("", Cow::Borrowed("plain")))
+        );
+        assert!(matches!(
+            super::nom_str("\"tab\\t\""),
+            Ok(("", Cow::Owned(s))) if s == "tab\t"
+        ));
+        assert!(matches!(
+            super::nom_str("\"run \\\"quoted\\\" \\u00e9\\\\ end\""),
+            Ok(("", Cow::Owned(s))) if s == "run \"quoted\" \u{e9}\\ end"
+        ));
+        assert!(super::nom_str("\"unterminated").is_err());
+    }
+
+    #[test]
+    fn array_elements_stack() {
+        use super::{Context, ParseOptions};
+        let s = "[1, [2, 3, [4, 5, 6, 7, 8]], [], 9]";
+        let opts = ParseOptions::default();
+        let cx = Context::new(&opts);
+        let (_, value) = cx.value(s).unwrap();
+        assert_eq!(value, json!([1, [2, 3, [4, 5, 6, 7, 8]], [], 9]));
+        // Every array takes its own elements back off the stack, which keeps its allocation for
+        // the arrays that follow.
+        assert!(cx.elements.borrow().is_empty());
+        assert!(cx.elements.borrow().capacity() >= 8);
+        // Each array is allocated once at its final size, even though the stack grew past it.
+        let mut arrays = vec![value.as_array().unwrap()];
+        while let Some(items) = arrays.pop() {
+            assert_eq!(items.capacity(), items.len());
+            arrays.extend(items.iter().filter_map(JsonValue::as_array));
+        }
+        assert_eq!(cx.value(s).unwrap().1, value);
+    }
+
+    #[test]
+    fn array_allow_trailing_comma() {
+        use super::{Context, ParseOptions};
+        use JsonValue::*;
+        let opts = ParseOptions {
+            allow_trailing_commas: true,
+            ..ParseOptions::default()
+        };
+        assert_eq!(
+            Context::new(&opts).array("[null, null, ]"),
+            Ok(("", JsonValue::Array(vec![Null, Null])))
+        );
+        assert!(Context::new(&opts).array("[,]").is_err());
+    }
+
     #[test]
     fn nom_integer() {
         assert_eq!(
-            super::nom_number("0234"),
-            Ok(("", JsonValue::Number(234.0)))
+            super::nom_number("234"),
+            Ok(("", JsonValue::Number(234u64.into())))
         );
     }
 
     #[test]
-    #[should_panic]
-    fn nom_bad_integer() {
+    fn nom_integer_with_zero() {
         assert_eq!(
-            super::nom_number("00234"),
-            Ok(("", JsonValue::Number(234.0)))
+            super::nom_number("10"),
+            Ok(("", JsonValue::Number(10u64.into())))
         );
     }
 
+    #[test]
+    fn nom_zero() {
+        assert_eq!(
+            super::nom_number("0"),
+            Ok(("", JsonValue::Number(0u64.into())))
+        );
+        assert_eq!(
+            super::nom_number("0.5"),
+            Ok(("", JsonValue::Number(0.5.into())))
+        );
+    }
+
+    #[test]
+    fn nom_leading_zero() {
+        assert!(super::nom_number("0234").is_err());
+    }
+
+    #[test]
+    fn nom_bad_integer() {
+        assert!(super::nom_number("00234").is_err());
+    }
+
     #[test]
     fn nom_float() {
         assert_eq!(
             super::nom_number("234.0123"),
-            Ok(("", JsonValue::Number(234.0123)))
+            Ok(("", JsonValue::Number(234.0123.into())))
         );
     }
 
@@ -241,30 +1263,151 @@ mod test {
     fn nom_float_negative() {
         assert_eq!(
             super::nom_number("-234.0123"),
-            Ok(("", JsonValue::Number(-234.0123)))
+            Ok(("", JsonValue::Number((-234.0123).into())))
+        );
+    }
+
+    #[test]
+    fn nom_large_integer() {
+        assert_eq!(
+            super::nom_number("1234567890"),
+            Ok(("", JsonValue::Number(1234567890u64.into())))
+        );
+    }
+
+    #[test]
+    fn nom_float_precision() {
+        assert_eq!(
+            super::nom_number("0.1"),
+            Ok(("", JsonValue::Number(0.1.into())))
+        );
+    }
+
+    #[test]
+    fn nom_negative_integer() {
+        assert_eq!(
+            super::nom_number("-42"),
+            Ok(("", JsonValue::Number((-42i64).into())))
+        );
+    }
+
+    #[test]
+    fn nom_integer_overflow() {
+        assert_eq!(
+            super::nom_number("18446744073709551616"),
+            Ok(("", JsonValue::Number(18446744073709551616.0.into())))
+        );
+    }
+
+    #[test]
+    fn nom_negative_zero() {
+        assert_eq!(
+            super::nom_number("-0"),
+            Ok(("", JsonValue::Number((-0.0).into())))
         );
     }
 
     #[test]
     fn nom_exponent() {
-        assert_eq!(super::nom_number("3e-2"), Ok(("", JsonValue::Number(0.03))));
+        assert_eq!(
+            super::nom_number("3e-2"),
+            Ok(("", JsonValue::Number(0.03.into())))
+        );
+    }
+
+    #[test]
+    fn nom_exponent_unsigned() {
+        assert_eq!(
+            super::nom_number("1e10"),
+            Ok(("", JsonValue::Number(1e10.into())))
+        );
+        assert_eq!(
+            super::nom_number("6.022E23"),
+            Ok(("", JsonValue::Number(6.022e23.into())))
+        );
+    }
+
+    #[test]
+    fn nom_malformed_numbers() {
+        for s in ["-", ".5", "+1", "1.", "1.e5", "1e", "1e+"] {
+            assert!(super::nom_number(s).is_err(), "{s:?} should not parse");
+        }
+    }
+
+    #[test]
+    fn nom_integer_bounds() {
+        let number = |s| super::nom_number(s).unwrap().1;
+        assert_eq!(number("9007199254740993").as_i64(), Some(9007199254740993));
+        assert_eq!(number("-9223372036854775808").as_i64(), Some(i64::MIN));
+        assert_eq!(number("9223372036854775808").as_i64(), None);
+        assert_eq!(number("18446744073709551615").as_u64(), Some(u64::MAX));
+        assert_eq!(
+            number("18446744073709551616"),
+            json!(18446744073709551616.0)
+        );
+        assert_eq!(
+            number("-9223372036854775809"),
+            json!(-9223372036854775809.0)
+        );
+        assert_eq!(number("1.0").as_i64(), None);
+    }
+
+    #[test]
+    fn nom_number_overflow() {
+        assert!(matches!(
+            super::nom_number("1e400"),
+            Err(nom::Err::Failure(_))
+        ));
+        assert!(super::nom_number("-1.5e309").is_err());
+        assert_eq!(super::nom_number("1e-400"), Ok(("", json!(0.0))));
+        assert_eq!(super::nom_number("1e308"), Ok(("", json!(1e308))));
+    }
+
+    #[test]
+    fn preserve_number_literals() {
+        use super::{Context, ParseOptions};
+        let s = "[ 1.0 , -0, 12345678901234567890123, 2.50e-3 ]";
+        let (_, plain) = super::nom_parse(s).unwrap();
+        assert_eq!(plain[0].as_number().unwrap().literal(), None);
+        if cfg!(feature = "num-bigint") {
+            assert_eq!(plain.to_string(), "[1,-0,12345678901234567890123,0.0025]");
+        } else {
+            assert_eq!(plain.to_string(), "[1,-0,1.2345678901234568e22,0.0025]");
+        }
+
+        let opts = ParseOptions {
+            preserve_number_literals: true,
+            ..ParseOptions::default()
+        };
+        for result in [
+            Context::new(&opts).value(s),
+            Context::new(&opts).deep_value(s),
+        ] {
+            let (_, value) = result.unwrap();
+            assert_eq!(value, plain);
+            assert_eq!(
+                value.to_string(),
+                "[1.0,-0,12345678901234567890123,2.50e-3]"
+            );
+        }
+    }
+
+    #[test]
+    fn non_finite_literals() {
+        for s in ["NaN", "Infinity", "-Infinity", "inf"] {
+            assert!(super::nom_parse(s).is_err(), "{s:?} should not parse");
+        }
     }
 
     #[test]
     fn nom_object() {
-        use super::JsonValue::*;
         assert_eq!(
             super::nom_object(
                 "{\"item1\":null, \"item2\": null , \"my num\":  45, \"my_list\": [] }"
             ),
             Ok((
                 "",
-                JsonValue::Object(vec![
-                    (String("item1".to_string()), Null),
-                    (String("item2".to_string()), Null),
-                    (String("my num".to_string()), Number(45.0)),
-                    (String("my_list".to_string()), Array(Vec::new()))
-                ])
+                json!({"item1": null, "item2": null, "my num": 45, "my_list": []})
             ))
         );
     }
@@ -276,26 +1419,247 @@ mod test {
             super::nom_object("{\"item1\":null }"),
             Ok((
                 "",
-                JsonValue::Object(vec![(String("item1".to_string()), Null),])
+                JsonValue::Object(vec![("item1".to_string(), Null),].into())
+            ))
+        );
+    }
+
+    #[test]
+    fn nom_object_trailing_comma() {
+        assert!(super::nom_object("{\"item1\":null, }").is_err());
+    }
+
+    #[test]
+    fn object_allow_trailing_comma() {
+        use super::{Context, JsonValue::*, ParseOptions};
+        let opts = ParseOptions {
+            allow_trailing_commas: true,
+            ..ParseOptions::default()
+        };
+        assert_eq!(
+            Context::new(&opts).object("{\"item1\":null, }"),
+            Ok((
+                "",
+                JsonValue::Object(vec![("item1".to_string(), Null)].into())
             ))
         );
     }
 
+    #[test]
+    fn reject_duplicate_keys() {
+        use super::{Context, ParseOptions};
+        let s = "{\"a\": 1, \"b\": {\"a\": 2}, \"a\": 3}";
+        assert!(super::nom_object(s).is_ok());
+
+        let opts = ParseOptions {
+            reject_duplicate_keys: true,
+            ..ParseOptions::default()
+        };
+        for result in [
+            Context::new(&opts).object(s),
+            Context::new(&opts).deep_value(s),
+        ] {
+            let Err(nom::Err::Failure(e)) = result else {
+                panic!("expected a failure");
+            };
+            assert_eq!(e.errors[0].0, "\"a\": 3}");
+        }
+        assert!(Context::new(&opts)
+            .deep_value("{\"a\": {\"a\": {}}}")
+            .is_ok());
+    }
+
+    #[test]
+    fn max_nodes() {
+        use super::{Context, ParseOptions};
+        let opts = ParseOptions {
+            max_nodes: Some(4),
+            ..ParseOptions::default()
+        };
+        let s = "[[], {\"a\": 1}]";
+        assert!(Context::new(&opts).value(s).is_ok());
+        assert!(Context::new(&opts).deep_value(s).is_ok());
+
+        // Arrays and objects are counted once complete when recursing, but as they open otherwise.
+        let s = "[1, [2, 3], 4]";
+        for (result, at) in [
+            (Context::new(&opts).value(s), "4]"),
+            (Context::new(&opts).deep_value(s), "3], 4]"),
+        ] {
+            let Err(nom::Err::Failure(e)) = result else {
+                panic!("expected a failure");
+            };
+            assert_eq!(e.errors[0].0, at);
+        }
+    }
+
+    #[test]
+    fn spanned() {
+        use crate::{Spanned, SpannedValue};
+
+        let s = " [\n  true ,{ \"k\" : -1.5e3 }] ";
+        let (_, root) = super::Context::new(&Default::default())
+            .spanned(s, s)
+            .unwrap();
+        let SpannedValue::Array(items) = root.value else {
+            panic!("expected an array");
+        };
+        assert_eq!((root.start, root.end), (1, s.len() - 1));
+        assert_eq!(&s[items[0].start..items[0].end], "true");
+        assert_eq!(&s[items[1].start..items[1].end], "{ \"k\" : -1.5e3 }");
+        let SpannedValue::Object(entries) = &items[1].value else {
+            panic!("expected an object");
+        };
+        let (key, value) = &entries[0];
+        assert_eq!(
+            *key,
+            Spanned {
+                value: "k".to_string(),
+                start: 13,
+                end: 16
+            }
+        );
+        assert_eq!(&s[value.start..value.end], "-1.5e3");
+
+        assert!(crate::parse_spanned("[1, }").is_err());
+    }
+
+    #[test]
+    fn dedup_keys() {
+        use super::{Context, ParseOptions};
+        let opts = ParseOptions {
+            dedup_keys: true,
+            ..ParseOptions::default()
+        };
+        let s = "{\"a\": 1, \"b\": {\"c\": 2, \"c\": 3}, \"a\": 4}";
+        let expected = json!({"a": 4, "b": {"c": 3}});
+        for result in [
+            Context::new(&opts).object(s),
+            Context::new(&opts).deep_value(s),
+        ] {
+            let (_, JsonValue::Object(map)) = result.unwrap() else {
+                panic!("expected an object");
+            };
+            assert_eq!(map.len(), 2);
+            assert_eq!(JsonValue::Object(map), expected);
+        }
+
+        let cx = Context::new(&opts);
+        assert!(cx.value(s).is_ok());
+        assert_eq!(cx.take_duplicates(), ["c", "a"]);
+        assert!(cx.take_duplicates().is_empty());
+    }
+
+    #[test]
+    fn allow_comments() {
+        use super::{Context, ParseOptions};
+        let s = "// leading\n{ /* a */ \"a\" /* b */ : /* c */ [1 // d\n, /**/ 2 ] // e\n, \"/* s */\": {/**/} } // end";
+        assert!(Context::new(&ParseOptions::default()).value(s).is_err());
+
+        let opts = ParseOptions {
+            allow_comments: true,
+            ..ParseOptions::default()
+        };
+        let expected = json!({"a": [1, 2], "/* s */": {}});
+        assert_eq!(Context::new(&opts).value(s), Ok(("", expected.clone())));
+        assert_eq!(Context::new(&opts).deep_value(s), Ok(("", expected)));
+        assert!(Context::new(&opts).value("[1 /* unterminated ]").is_err());
+        assert!(Context::new(&opts).value("[1/**/2]").is_err());
+    }
+
+    #[test]
+    fn skip_leading_comment_lines() {
+        use super::{Context, ParseOptions};
+        let s = "#!/usr/bin/env jq\n// generated, do not edit\n\n  # v2\r\n{\"a\": [1, 2]}";
+        let opts = ParseOptions::default();
+        let cx = Context::new(&opts);
+        assert_eq!(cx.header(s), s);
+        assert!(cx.value(cx.header(s)).is_err());
+
+        let opts = ParseOptions {
+            skip_leading_comment_lines: true,
+            ..ParseOptions::default()
+        };
+        let cx = Context::new(&opts);
+        assert_eq!(cx.value(cx.header(s)), Ok(("", json!({"a": [1, 2]}))));
+        assert_eq!(cx.header("  [1]"), "  [1]");
+        assert_eq!(cx.header("# only a header"), "");
+        assert!(cx.value(cx.header("[1 // two\n]")).is_err());
+        assert!(cx.value(cx.header("[1,\n# two\n2]")).is_err());
+    }
+
+    #[test]
+    fn allow_single_quotes() {
+        use super::{Context, ParseOptions};
+        let s = r#"{'a': ['it\'s', "\"q\"", 'say "hi"'], "b": '\u00e9\n'}"#;
+        assert!(Context::new(&ParseOptions::default()).value(s).is_err());
+
+        let opts = ParseOptions {
+            allow_single_quotes: true,
+            ..ParseOptions::default()
+        };
+        let expected = json!({"a": ["it's", "\"q\"", "say \"hi\""], "b": "\u{e9}\n"});
+        assert_eq!(Context::new(&opts).value(s), Ok(("", expected.clone())));
+        assert_eq!(Context::new(&opts).deep_value(s), Ok(("", expected)));
+        assert!(Context::new(&opts).value("'a\"").is_err());
+        assert!(Context::new(&opts).value("\"\\'\"").is_err());
+    }
+
+    #[test]
+    fn allow_unquoted_keys() {
+        use super::{Context, ParseOptions};
+        let s = r#"{ name: "x", _private1: [], $ref: {"quoted": 1, été: 2} }"#;
+        assert!(Context::new(&ParseOptions::default()).value(s).is_err());
+
+        let opts = ParseOptions {
+            allow_unquoted_keys: true,
+            ..ParseOptions::default()
+        };
+        let expected = json!({"name": "x", "_private1": [], "$ref": {"quoted": 1, "été": 2}});
+        assert_eq!(Context::new(&opts).value(s), Ok(("", expected.clone())));
+        assert_eq!(Context::new(&opts).deep_value(s), Ok(("", expected)));
+        assert!(Context::new(&opts).value("{1a: 1}").is_err());
+        assert!(Context::new(&opts).value("{a-b: 1}").is_err());
+        assert!(Context::new(&opts).value("[a]").is_err());
+    }
+
+    #[test]
+    fn nom_object_context() {
+        use nom::error::VerboseErrorKind::Context;
+        let Err(nom::Err::Failure(e)) = super::nom_object("{\"a\": [1, {\"b\": nul}]}") else {
+            panic!("expected a failure");
+        };
+        let contexts: Vec<_> = e
+            .errors
+            .iter()
+            .filter_map(|(_, kind)| match kind {
+                Context(c) => Some(*c),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(
+            contexts,
+            [
+                "object",
+                "object value",
+                "object",
+                "array element",
+                "array",
+                "object value",
+                "object"
+            ]
+        );
+    }
+
     #[test]
     fn nom_object_nested() {
-        use super::JsonValue::*;
         assert_eq!(
             super::nom_object(
                 "{\"item1\":null, \"item2\": null , \"my num\":  45, \"my_obj\": {} }"
             ),
             Ok((
                 "",
-                JsonValue::Object(vec![
-                    (String("item1".to_string()), Null),
-                    (String("item2".to_string()), Null),
-                    (String("my num".to_string()), Number(45.0)),
-                    (String("my_obj".to_string()), Object(Vec::new()))
-                ])
+                json!({"item1": null, "item2": null, "my num": 45, "my_obj": {}})
             ))
         );
     }
@@ -0,0 +1,137 @@
+//! The raw [`nom`] parsers behind the functions at the crate root, for embedding JSON in a
+//! larger `nom` grammar.
+//!
+//! Unlike [`parse`](crate::parse) and friends, these return the unparsed rest of the input and
+//! the [`VerboseError`] as it is, so that they compose with other combinators. Each consumes any
+//! whitespace around the value, except for the `_exact` scalar parsers, which match only the value
+//! itself for formats where whitespace is significant. They are built on `nom` 7, so must be used
+//! with that version.
+//!
+//! ```
+//! use jsnom::{json, parsers};
+//! use nom::{bytes::complete::tag, sequence::preceded};
+//!
+//! let mut assignment = preceded(tag("let x ="), parsers::value);
+//! assert_eq!(assignment("let x = [1, 2];"), Ok((";", json!([1, 2]))));
+//! ```
+
+use nom::{error::VerboseError, IResult};
+
+use crate::{parse, JsonValue, ParseOptions};
+
+/// The result of every parser in this module: the rest of the input along with the output, or
+/// the error.
+pub type ParseResult<'a, O = JsonValue> = IResult<&'a str, O, VerboseError<&'a str>>;
+
+/// Parses any JSON value.
+pub fn value(s: &str) -> ParseResult<'_> {
+    parse::nom_parse(s)
+}
+
+/// Parses any JSON value, using the given [`ParseOptions`].
+///
+/// ```
+/// use jsnom::{json, parsers, ParseOptions};
+///
+/// let opts = ParseOptions {
+///     allow_trailing_commas: true,
+///     ..ParseOptions::default()
+/// };
+/// let mut value = parsers::value_with(&opts);
+/// assert_eq!(value("[1, 2,] rest"), Ok(("rest", json!([1, 2]))));
+/// ```
+pub fn value_with<'a, 'o>(opts: &'o ParseOptions) -> impl FnMut(&'a str) -> ParseResult<'a> + 'o {
+    move |s| parse::Context::new(opts).value(s)
+}
+
+/// Parses `null`.
+pub fn null(s: &str) -> ParseResult<'_> {
+    parse::nom_null(s)
+}
+
+/// Parses `true` or `false`.
+pub fn boolean(s: &str) -> ParseResult<'_> {
+    parse::nom_bool(s)
+}
+
+/// Parses `null` at the very start of the input, leaving any whitespace after it.
+pub fn null_exact(s: &str) -> ParseResult<'_> {
+    parse::nom_null_exact(s)
+}
+
+/// Parses `true` or `false` at the very start of the input, leaving any whitespace after it.
+pub fn boolean_exact(s: &str) -> ParseResult<'_> {
+    parse::nom_bool_exact(s)
+}
+
+/// Parses a string, decoding its escapes.
+pub fn string(s: &str) -> ParseResult<'_> {
+    parse::nom_string(s)
+}
+
+/// Parses a number.
+pub fn number(s: &str) -> ParseResult<'_> {
+    parse::nom_number(s)
+}
+
+/// Parses a number at the very start of the input, leaving any whitespace after it.
+///
+/// ```
+/// use jsnom::{parsers, JsonValue};
+///
+/// assert_eq!(parsers::number_exact("3 "), Ok((" ", JsonValue::from(3))));
+/// assert!(parsers::number_exact(" 3").is_err());
+/// ```
+pub fn number_exact(s: &str) -> ParseResult<'_> {
+    parse::nom_number_exact(s)
+}
+
+/// Parses an array.
+pub fn array(s: &str) -> ParseResult<'_> {
+    parse::nom_array(s)
+}
+
+/// Parses an object.
+pub fn object(s: &str) -> ParseResult<'_> {
+    parse::nom_object(s)
+}
+
+#[cfg(test)]
+mod test {
+    use nom::{branch::alt, multi::many1};
+
+    use crate::JsonValue;
+
+    #[test]
+    fn compose() {
+        let mut scalars = many1(alt((
+            super::null,
+            super::boolean,
+            super::number,
+            super::string,
+        )));
+        assert_eq!(
+            scalars(" null true -1.5 \"s\" [1]"),
+            Ok((
+                "[1]",
+                vec![
+                    JsonValue::Null,
+                    JsonValue::Bool(true),
+                    JsonValue::from(-1.5),
+                    JsonValue::from("s")
+                ]
+            ))
+        );
+        assert_eq!(super::object("{} x"), Ok(("x", json!({}))));
+
+        assert_eq!(super::null_exact("null\n"), Ok(("\n", JsonValue::Null)));
+        assert_eq!(
+            super::boolean_exact("false\t"),
+            Ok(("\t", JsonValue::Bool(false)))
+        );
+        assert_eq!(super::number_exact("-1.5e3,"), Ok((",", json!(-1500.0))));
+        assert!(super::null_exact(" null").is_err());
+        assert!(super::boolean_exact(" true").is_err());
+        assert!(super::array("{}").is_err());
+    }
+}
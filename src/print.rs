@@ -0,0 +1,544 @@
+use alloc::{format, string::String, vec::Vec};
+use core::fmt::{self, Write};
+#[cfg(feature = "std")]
+use std::io;
+
+use crate::{JsonValue, Number};
+
+/// Serializes the value as compact JSON, so that it can be parsed back into an equal value.
+///
+/// JSON has no representation for `NaN` or infinities, so non-finite numbers are written as
+/// `null`.
+///
+/// ```
+/// use jsnom::JsonValue;
+///
+/// let value = JsonValue::Array(vec![
+///     JsonValue::Number(1u64.into()),
+///     JsonValue::String("two\n".to_string()),
+///     JsonValue::Null,
+/// ]);
+/// assert_eq!(value.to_string(), "[1,\"two\\n\",null]");
+/// ```
+impl fmt::Display for JsonValue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            JsonValue::Null => f.write_str("null"),
+            JsonValue::Bool(b) => write!(f, "{b}"),
+            JsonValue::String(s) => write_string(f, s),
+            JsonValue::Number(n) => write_number(f, n),
+            JsonValue::Array(items) => {
+                f.write_char('[')?;
+                for (i, item) in items.iter().enumerate() {
+                    if i > 0 {
+                        f.write_char(',')?;
+                    }
+                    write!(f, "{item}")?;
+                }
+                f.write_char(']')
+            }
+            JsonValue::Object(entries) => {
+                f.write_char('{')?;
+                for (i, (key, value)) in entries.iter().enumerate() {
+                    if i > 0 {
+                        f.write_char(',')?;
+                    }
+                    write_string(f, key)?;
+                    write!(f, ":{value}")?;
+                }
+                f.write_char('}')
+            }
+        }
+    }
+}
+
+impl JsonValue {
+    /// Serialize the value as indented JSON, using `indent` spaces per level of nesting.
+    ///
+    /// ```
+    /// use jsnom::JsonValue;
+    ///
+    /// let value = JsonValue::Array(vec![JsonValue::Null, JsonValue::Array(Vec::new())]);
+    /// assert_eq!(value.to_pretty_string(2), "[\n  null,\n  []\n]");
+    /// ```
+    pub fn to_pretty_string(&self, indent: usize) -> String {
+        self.to_pretty_string_with(&" ".repeat(indent))
+    }
+
+    /// Serialize the value as indented JSON, repeating `indent` once per level of nesting.
+    ///
+    /// ```
+    /// use jsnom::JsonValue;
+    ///
+    /// let value = JsonValue::Object(vec![("a".to_string(), JsonValue::Bool(true))].into());
+    /// assert_eq!(value.to_pretty_string_with("\t"), "{\n\t\"a\": true\n}");
+    /// ```
+    pub fn to_pretty_string_with(&self, indent: &str) -> String {
+        let mut out = String::new();
+        write_pretty(&mut out, self, indent, 0).expect("writing to a String cannot fail");
+        out
+    }
+
+    /// Serialize the value as compact JSON straight into `w`, the same as [`Display`](fmt::Display)
+    /// but without building a `String` first. Output is written in many small pieces, so `w`
+    /// should usually be buffered.
+    ///
+    /// ```
+    /// use jsnom::json;
+    ///
+    /// let mut out = Vec::new();
+    /// json!({"a": [1, null]}).write_to(&mut out).unwrap();
+    /// assert_eq!(out, b"{\"a\":[1,null]}");
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn write_to<W: io::Write>(&self, w: &mut W) -> io::Result<()> {
+        let mut adapter = IoAdapter {
+            inner: w,
+            error: None,
+        };
+        write!(adapter, "{self}").map_err(|_| adapter.into_error())
+    }
+
+    /// Serialize the value as indented JSON straight into `w`, the same as
+    /// [`JsonValue::to_pretty_string`] but without building a `String` first.
+    ///
+    /// ```
+    /// use jsnom::json;
+    ///
+    /// let mut out = Vec::new();
+    /// json!([true]).write_pretty_to(&mut out, 2).unwrap();
+    /// assert_eq!(out, b"[\n  true\n]");
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn write_pretty_to<W: io::Write>(&self, w: &mut W, indent: usize) -> io::Result<()> {
+        let mut adapter = IoAdapter {
+            inner: w,
+            error: None,
+        };
+        write_pretty(&mut adapter, self, &" ".repeat(indent), 0).map_err(|_| adapter.into_error())
+    }
+
+    /// Render the value as an indented tree, with the type of every value spelled out, for
+    /// reading when debugging.
+    ///
+    /// ```
+    /// use jsnom::JsonValue;
+    ///
+    /// let value = JsonValue::from_str("{\"a\": [1, \"x\"], \"b\": null}").unwrap();
+    /// assert_eq!(
+    ///     value.display_tree(),
+    ///     "object (2 entries)\n  \"a\": array (2 items)\n    [0]: number 1\n    [1]: string \"x\"\n  \"b\": null"
+    /// );
+    /// ```
+    pub fn display_tree(&self) -> String {
+        let mut out = String::new();
+        write_tree(&mut out, self, 0).expect("writing to a String cannot fail");
+        out
+    }
+
+    /// Serialize the value in the canonical form of
+    /// [RFC 8785](https://datatracker.ietf.org/doc/html/rfc8785) (JCS), so that equal values
+    /// always serialize to the same string. Object keys are sorted, there is no whitespace, and
+    /// numbers are written as JavaScript would write them, going through an `f64`.
+    ///
+    /// ```
+    /// use jsnom::JsonValue;
+    ///
+    /// let a = JsonValue::from_str("{\"b\": [1.0, 1e21], \"a\": 0.000001}").unwrap();
+    /// let b = JsonValue::from_str("{\"a\": 1E-6, \"b\": [1, 1000000000000000000000]}").unwrap();
+    /// assert_eq!(a.to_canonical_string(), "{\"a\":0.000001,\"b\":[1,1e+21]}");
+    /// assert_eq!(a.to_canonical_string(), b.to_canonical_string());
+    /// ```
+    pub fn to_canonical_string(&self) -> String {
+        let mut out = String::new();
+        write_canonical(&mut out, self).expect("writing to a String cannot fail");
+        out
+    }
+}
+
+fn write_tree(f: &mut impl Write, value: &JsonValue, depth: usize) -> fmt::Result {
+    let count = |n: usize, one, many| format!("{n} {}", if n == 1 { one } else { many });
+    match value {
+        JsonValue::Null => f.write_str("null"),
+        JsonValue::Bool(b) => write!(f, "bool {b}"),
+        JsonValue::Number(n) => write!(f, "number {n}"),
+        JsonValue::String(s) => {
+            f.write_str("string ")?;
+            write_string(f, s)
+        }
+        JsonValue::Array(items) => {
+            write!(f, "array ({})", count(items.len(), "item", "items"))?;
+            for (i, item) in items.iter().enumerate() {
+                write!(f, "\n{}[{i}]: ", "  ".repeat(depth + 1))?;
+                write_tree(f, item, depth + 1)?;
+            }
+            Ok(())
+        }
+        JsonValue::Object(entries) => {
+            write!(f, "object ({})", count(entries.len(), "entry", "entries"))?;
+            for (key, value) in entries.iter() {
+                write!(f, "\n{}", "  ".repeat(depth + 1))?;
+                write_string(f, key)?;
+                f.write_str(": ")?;
+                write_tree(f, value, depth + 1)?;
+            }
+            Ok(())
+        }
+    }
+}
+
+fn write_canonical(f: &mut impl Write, value: &JsonValue) -> fmt::Result {
+    match value {
+        JsonValue::Number(n) => write_canonical_number(f, n.as_f64()),
+        JsonValue::Array(items) => {
+            f.write_char('[')?;
+            for (i, item) in items.iter().enumerate() {
+                if i > 0 {
+                    f.write_char(',')?;
+                }
+                write_canonical(f, item)?;
+            }
+            f.write_char(']')
+        }
+        JsonValue::Object(entries) => {
+            // Keys are compared by their UTF-16 code units, as in JavaScript.
+            let mut entries: Vec<_> = entries.iter().collect();
+            entries.sort_by(|(a, _), (b, _)| a.encode_utf16().cmp(b.encode_utf16()));
+            f.write_char('{')?;
+            for (i, (key, value)) in entries.into_iter().enumerate() {
+                if i > 0 {
+                    f.write_char(',')?;
+                }
+                write_string(f, key)?;
+                f.write_char(':')?;
+                write_canonical(f, value)?;
+            }
+            f.write_char('}')
+        }
+        // Strings are already escaped the way RFC 8785 asks for.
+        value => write!(f, "{value}"),
+    }
+}
+
+// JavaScript's `Number.prototype.toString`, which RFC 8785 uses for numbers. It is built from the
+// shortest digits that round-trip, which Rust's `{:e}` formatting also gives.
+fn write_canonical_number(f: &mut impl Write, value: f64) -> fmt::Result {
+    if !value.is_finite() {
+        return f.write_str("null");
+    }
+    if value == 0.0 {
+        return f.write_char('0');
+    }
+    if value < 0.0 {
+        f.write_char('-')?;
+    }
+    let scientific = format!("{:e}", value.abs());
+    let (mantissa, exponent) = scientific.split_once('e').unwrap();
+    let digits = mantissa.replace('.', "");
+    let k = digits.len() as i32;
+    // The decimal point goes after the first `n` digits.
+    let n = exponent.parse::<i32>().unwrap() + 1;
+    if k <= n && n <= 21 {
+        write!(f, "{digits}{}", "0".repeat((n - k) as usize))
+    } else if 0 < n && n <= 21 {
+        let (int, frac) = digits.split_at(n as usize);
+        write!(f, "{int}.{frac}")
+    } else if -6 < n && n <= 0 {
+        write!(f, "0.{}{digits}", "0".repeat(-n as usize))
+    } else {
+        let (first, rest) = digits.split_at(1);
+        f.write_str(first)?;
+        if !rest.is_empty() {
+            write!(f, ".{rest}")?;
+        }
+        write!(f, "e{}{}", if n > 0 { '+' } else { '-' }, (n - 1).abs())
+    }
+}
+
+// Lets the `fmt::Write` based serializers write to an `io::Write`, keeping hold of the first
+// error since `fmt::Error` can't carry one.
+#[cfg(feature = "std")]
+struct IoAdapter<'w, W> {
+    inner: &'w mut W,
+    error: Option<io::Error>,
+}
+
+#[cfg(feature = "std")]
+impl<W: io::Write> Write for IoAdapter<'_, W> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.inner.write_all(s.as_bytes()).map_err(|e| {
+            self.error = Some(e);
+            fmt::Error
+        })
+    }
+}
+
+#[cfg(feature = "std")]
+impl<W> IoAdapter<'_, W> {
+    fn into_error(self) -> io::Error {
+        self.error
+            .unwrap_or_else(|| io::Error::other("formatter error"))
+    }
+}
+
+fn write_pretty(f: &mut impl Write, value: &JsonValue, indent: &str, depth: usize) -> fmt::Result {
+    let newline = |f: &mut dyn Write, depth: usize| -> fmt::Result {
+        f.write_char('\n')?;
+        for _ in 0..depth {
+            f.write_str(indent)?;
+        }
+        Ok(())
+    };
+
+    match value {
+        JsonValue::Array(items) if !items.is_empty() => {
+            f.write_char('[')?;
+            for (i, item) in items.iter().enumerate() {
+                if i > 0 {
+                    f.write_char(',')?;
+                }
+                newline(f, depth + 1)?;
+                write_pretty(f, item, indent, depth + 1)?;
+            }
+            newline(f, depth)?;
+            f.write_char(']')
+        }
+        JsonValue::Object(entries) if !entries.is_empty() => {
+            f.write_char('{')?;
+            for (i, (key, value)) in entries.iter().enumerate() {
+                if i > 0 {
+                    f.write_char(',')?;
+                }
+                newline(f, depth + 1)?;
+                write_string(f, key)?;
+                f.write_str(": ")?;
+                write_pretty(f, value, indent, depth + 1)?;
+            }
+            newline(f, depth)?;
+            f.write_char('}')
+        }
+        // Scalars and empty containers look the same as in the compact form.
+        value => write!(f, "{value}"),
+    }
+}
+
+fn write_number(f: &mut impl Write, n: &Number) -> fmt::Result {
+    // A literal is always valid JSON, even for an integer too large for an `f64`.
+    if n.literal().is_some() || n.as_f64().is_finite() {
+        write!(f, "{n}")
+    } else {
+        f.write_str("null")
+    }
+}
+
+fn write_string(f: &mut impl Write, s: &str) -> fmt::Result {
+    f.write_char('"')?;
+    write_escaped(f, s)?;
+    f.write_char('"')
+}
+
+/// Escape a string for use inside a JSON string literal, the same way as when a
+/// [`JsonValue::String`] is written out. The result doesn't include the surrounding quotes.
+///
+/// ```
+/// use jsnom::escape_str;
+///
+/// let name = "say \"hi\"\n";
+/// assert_eq!(escape_str(name), "say \\\"hi\\\"\\n");
+/// assert_eq!(
+///     format!("{{\"name\":\"{}\"}}", escape_str(name)),
+///     jsnom::json!({"name": name}).to_string()
+/// );
+/// ```
+pub fn escape_str(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    write_escaped(&mut escaped, s).expect("writing to a `String` can't fail");
+    escaped
+}
+
+/// Write a string to `w` escaped as by [`escape_str`], without the surrounding quotes.
+pub fn write_escaped<W: Write>(w: &mut W, s: &str) -> fmt::Result {
+    for c in s.chars() {
+        match c {
+            '"' => w.write_str("\\\"")?,
+            '\\' => w.write_str("\\\\")?,
+            '\u{0008}' => w.write_str("\\b")?,
+            '\u{000c}' => w.write_str("\\f")?,
+            '\n' => w.write_str("\\n")?,
+            '\r' => w.write_str("\\r")?,
+            '\t' => w.write_str("\\t")?,
+            c if c < '\u{0020}' => write!(w, "\\u{:04x}", c as u32)?,
+            c => w.write_char(c)?,
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use proptest::{collection::vec, prelude::*};
+
+    use crate::{
+        JsonValue::{self, *},
+        Map,
+    };
+
+    #[test]
+    fn display_scalars() {
+        assert_eq!(Null.to_string(), "null");
+        assert_eq!(Bool(false).to_string(), "false");
+        assert_eq!(Number(5u64.into()).to_string(), "5");
+        assert_eq!(Number(5.0.into()).to_string(), "5");
+        assert_eq!(Number((-0.0).into()).to_string(), "-0");
+        assert_eq!(Number(1e15.into()).to_string(), "1000000000000000");
+        assert_eq!(Number((-0.25).into()).to_string(), "-0.25");
+        assert_eq!(Number(0.1.into()).to_string(), "0.1");
+        assert_eq!(Number(0.1f32.into()).to_string(), "0.1");
+        assert_eq!(Number((1.0 / 3.0).into()).to_string(), "0.3333333333333333");
+        assert_eq!(Number(1e21.into()).to_string(), "1e21");
+        assert_eq!(Number(1e-7.into()).to_string(), "1e-7");
+        assert_eq!(Number(f32::INFINITY.into()).to_string(), "null");
+        assert_eq!(Number(f64::NAN.into()).to_string(), "null");
+    }
+
+    #[test]
+    fn display_string_escapes() {
+        assert_eq!(
+            String("\"\\\u{0008}\u{000c}\n\r\t\u{0001}é".to_string()).to_string(),
+            "\"\\\"\\\\\\b\\f\\n\\r\\t\\u0001é\""
+        );
+    }
+
+    #[test]
+    fn display_object() {
+        let value = Object(
+            vec![
+                ("a".to_string(), Array(vec![Null, Bool(true)])),
+                ("b".to_string(), Object(Map::new())),
+            ]
+            .into(),
+        );
+        assert_eq!(value.to_string(), "{\"a\":[null,true],\"b\":{}}");
+    }
+
+    #[test]
+    fn pretty_nested() {
+        let value = Object(
+            vec![
+                (
+                    "a".to_string(),
+                    Array(vec![Number(1u64.into()), Array(vec![])]),
+                ),
+                (
+                    "b".to_string(),
+                    Object(vec![("c".to_string(), Null)].into()),
+                ),
+                ("d".to_string(), Object(Map::new())),
+            ]
+            .into(),
+        );
+        assert_eq!(
+            value.to_pretty_string(2),
+            "{\n  \"a\": [\n    1,\n    []\n  ],\n  \"b\": {\n    \"c\": null\n  },\n  \"d\": {}\n}"
+        );
+    }
+
+    #[test]
+    fn write_to_error() {
+        let mut buf = [0u8; 4];
+        let error = json!([1, 2, 3])
+            .write_to(&mut buf.as_mut_slice())
+            .unwrap_err();
+        assert_eq!(error.kind(), std::io::ErrorKind::WriteZero);
+        assert_eq!(&buf, b"[1,2");
+    }
+
+    #[test]
+    fn pretty_scalar() {
+        assert_eq!(Bool(true).to_pretty_string(4), "true");
+    }
+
+    #[test]
+    fn canonical_numbers() {
+        // Examples from RFC 8785, appendix B.
+        for (n, expected) in [
+            (0.0, "0"),
+            (-0.0, "0"),
+            (5e-324, "5e-324"),
+            (-1.7976931348623157e308, "-1.7976931348623157e+308"),
+            (9007199254740992.0, "9007199254740992"),
+            (295147905179352830000.0, "295147905179352830000"),
+            (1e21, "1e+21"),
+            (1e-7, "1e-7"),
+            (0.000001, "0.000001"),
+            (333333333.3333333, "333333333.3333333"),
+            (1e23, "1e+23"),
+            (-123.5, "-123.5"),
+            (f64::NAN, "null"),
+        ] {
+            assert_eq!(Number(n.into()).to_canonical_string(), expected);
+        }
+    }
+
+    #[test]
+    fn canonical_key_order() {
+        let value = json!({"\u{e9}": 1, "\u{1f600}": 2, "\u{ff61}": 3, "b": {"z": null, "a": []}, "a": "\n"});
+        assert_eq!(
+            value.to_canonical_string(),
+            "{\"a\":\"\\n\",\"b\":{\"a\":[],\"z\":null},\"\u{e9}\":1,\"\u{1f600}\":2,\"\u{ff61}\":3}"
+        );
+    }
+
+    #[test]
+    fn display_round_trip() {
+        let value = Object(
+            vec![
+                (
+                    "escapes".to_string(),
+                    String("tab\there \"quoted\" \u{001f}".to_string()),
+                ),
+                (
+                    "numbers".to_string(),
+                    Array(vec![
+                        Number(0.1.into()),
+                        Number((-3e-20).into()),
+                        Number(1234567890u64.into()),
+                        Number((-7i64).into()),
+                    ]),
+                ),
+            ]
+            .into(),
+        );
+        assert_eq!(JsonValue::from_str(&value.to_string()), Ok(value));
+    }
+
+    /// Generates trees of arrays and objects with finite numbers, nested at most four deep.
+    fn json_value() -> impl Strategy<Value = JsonValue> {
+        let leaf = prop_oneof![
+            Just(Null),
+            any::<bool>().prop_map(Bool),
+            any::<std::string::String>().prop_map(String),
+            any::<u64>().prop_map(|n| Number(n.into())),
+            any::<i64>().prop_map(|n| Number(n.into())),
+            any::<f64>()
+                .prop_filter("JSON numbers are finite", |n| n.is_finite())
+                .prop_map(|n| Number(n.into())),
+        ];
+        leaf.prop_recursive(4, 64, 8, |inner| {
+            prop_oneof![
+                vec(inner.clone(), 0..8).prop_map(Array),
+                vec((any::<std::string::String>(), inner), 0..8)
+                    .prop_map(|entries| Object(entries.into())),
+            ]
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn display_round_trip_any(value in json_value()) {
+            let (compact, pretty) = (value.to_string(), value.to_pretty_string(2));
+            prop_assert_eq!(crate::parse(&compact), Ok(value.clone()));
+            prop_assert_eq!(crate::parse(&pretty), Ok(value));
+        }
+    }
+}
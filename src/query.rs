@@ -0,0 +1,126 @@
+use alloc::{vec, vec::Vec};
+
+use crate::{access::array_index, JsonValue};
+
+/// One step of a path given to [`JsonValue::query`].
+enum Step<'p> {
+    /// `.key`
+    Child(&'p str),
+    /// `..key`
+    Descendant(&'p str),
+    /// `[0]`
+    Index(usize),
+    /// `[*]` or `.*`
+    Wildcard,
+    /// `..*`
+    AllDescendants,
+}
+
+impl JsonValue {
+    /// Find every value matching a path in a small subset of
+    /// [JSONPath](https://www.rfc-editor.org/rfc/rfc9535), in document order. Paths start with `$`
+    /// for the whole value, followed by any number of:
+    ///
+    /// - `.key` for the value of a key in an object,
+    /// - `[0]` for an element of an array,
+    /// - `[*]` or `.*` for every element of an array or value in an object,
+    /// - `..key` for the value of the key in this or any nested object, and `..*` for every
+    ///   nested value.
+    ///
+    /// A malformed path matches nothing.
+    ///
+    /// ```
+    /// use jsnom::json;
+    ///
+    /// let response = json!({
+    ///     "data": {"users": [{"id": 1, "name": "a"}, {"id": 2, "name": "b"}]},
+    ///     "meta": {"id": "req-1"},
+    /// });
+    /// assert_eq!(response.query("$.data.users[1].name"), [&json!("b")]);
+    /// assert_eq!(response.query("$.data.users[*].id"), [&json!(1), &json!(2)]);
+    /// assert_eq!(response.query("$..id"), [&json!(1), &json!(2), &json!("req-1")]);
+    /// assert!(response.query("data.users").is_empty());
+    /// ```
+    pub fn query(&self, path: &str) -> Vec<&JsonValue> {
+        let Some(steps) = parse_path(path) else {
+            return Vec::new();
+        };
+        let mut matches = vec![self];
+        for step in steps {
+            matches = matches
+                .into_iter()
+                .flat_map(|value| step.apply(value))
+                .collect();
+        }
+        matches
+    }
+}
+
+impl Step<'_> {
+    fn apply<'v>(&self, value: &'v JsonValue) -> Vec<&'v JsonValue> {
+        match self {
+            Step::Child(key) => value.get(*key).into_iter().collect(),
+            Step::Index(i) => value.get(*i).into_iter().collect(),
+            Step::Wildcard => match value {
+                JsonValue::Array(items) => items.iter().collect(),
+                JsonValue::Object(map) => map.values().collect(),
+                _ => Vec::new(),
+            },
+            Step::Descendant(key) => value.descendants().filter_map(|v| v.get(*key)).collect(),
+            Step::AllDescendants => value.descendants().skip(1).collect(),
+        }
+    }
+}
+
+fn parse_path(path: &str) -> Option<Vec<Step<'_>>> {
+    let mut rest = path.strip_prefix('$')?;
+    let mut steps = Vec::new();
+    while !rest.is_empty() {
+        let step;
+        (step, rest) = if let Some(after) = rest.strip_prefix("..") {
+            match name(after)? {
+                ("*", after) => (Step::AllDescendants, after),
+                (key, after) => (Step::Descendant(key), after),
+            }
+        } else if let Some(after) = rest.strip_prefix('.') {
+            match name(after)? {
+                ("*", after) => (Step::Wildcard, after),
+                (key, after) => (Step::Child(key), after),
+            }
+        } else {
+            let (index, after) = rest.strip_prefix('[')?.split_once(']')?;
+            match index {
+                "*" => (Step::Wildcard, after),
+                index => (Step::Index(array_index(index)?), after),
+            }
+        };
+        steps.push(step);
+    }
+    Some(steps)
+}
+
+// A key runs up to the next `.` or `[`, and can't be empty.
+fn name(s: &str) -> Option<(&str, &str)> {
+    let end = s.find(['.', '[']).unwrap_or(s.len());
+    (end > 0).then(|| s.split_at(end))
+}
+
+#[cfg(test)]
+mod test {
+    #[test]
+    fn query() {
+        let value = json!({"a": {"b": [1, {"b": 2}]}, "c": [[3], {"d": 4}]});
+        assert_eq!(value.query("$"), [&value]);
+        assert_eq!(value.query("$.a.b[0]"), [&json!(1)]);
+        assert_eq!(value.query("$..b").len(), 2);
+        assert_eq!(value.query("$..b[1].b"), [&json!(2)]);
+        assert_eq!(value.query("$.c[*][0]"), [&json!(3)]);
+        assert_eq!(value.query("$.c.*.d"), [&json!(4)]);
+        assert_eq!(value.query("$..*").len(), 10);
+        assert!(value.query("$.c[2]").is_empty());
+        assert!(value.query("$.c[01]").is_empty());
+        assert!(value.query("$.").is_empty());
+        assert!(value.query("$a").is_empty());
+        assert!(value.query("$[0").is_empty());
+    }
+}
@@ -0,0 +1,268 @@
+use alloc::{string::String, vec::Vec};
+
+use nom::{
+    branch::alt,
+    character::complete::char,
+    error::{context, ContextError, ErrorKind, ParseError, VerboseError},
+    IResult,
+};
+
+use crate::{parse, Error, JsonValue, Map};
+
+/// A best-effort parser for [`parse_recover`](crate::parse_recover). It follows the same grammar
+/// as the other parsers, but on a failure it records the error and skips ahead to the next `,`,
+/// `]` or `}` rather than giving up.
+pub(crate) struct Recover<'a> {
+    input: &'a str,
+    errors: Vec<Error<'a>>,
+    depth: usize,
+    // Set once the input is too deeply nested to carry on, so that the arrays and objects left
+    // open don't each report that they weren't closed.
+    abandoned: bool,
+}
+
+/// Deeper than this, recovery gives up rather than risk overflowing the stack.
+const MAX_DEPTH: usize = 128;
+
+impl<'a> Recover<'a> {
+    pub(crate) fn new(input: &'a str) -> Self {
+        Self {
+            input,
+            errors: Vec::new(),
+            depth: 0,
+            abandoned: false,
+        }
+    }
+
+    /// Parses a whole document, which must be a single value with only whitespace after it.
+    pub(crate) fn document(mut self, s: &'a str) -> (Option<JsonValue>, Vec<Error<'a>>) {
+        let (rest, value) = self.value(s);
+        let rest = skip_ws(rest);
+        if !rest.is_empty() {
+            self.error(VerboseError::from_error_kind(rest, ErrorKind::Eof));
+        }
+        (value, self.errors)
+    }
+
+    fn error(&mut self, e: VerboseError<&'a str>) {
+        if !self.abandoned {
+            self.errors.push(Error::from_raw(self.input, e));
+        }
+    }
+
+    // Records the error from a parser that is expected to fail.
+    fn fail<O>(&mut self, result: IResult<&'a str, O, VerboseError<&'a str>>) {
+        if let Err(nom::Err::Error(e) | nom::Err::Failure(e)) = result {
+            self.error(e);
+        }
+    }
+
+    fn value(&mut self, s: &'a str) -> (&'a str, Option<JsonValue>) {
+        let s = skip_ws(s);
+        if s.is_empty() {
+            self.error(VerboseError::from_error_kind(s, ErrorKind::Eof));
+            return (s, None);
+        }
+        if s.starts_with(['[', '{']) {
+            if self.depth == MAX_DEPTH {
+                self.error(VerboseError::add_context(
+                    s,
+                    "maximum nesting depth exceeded",
+                    VerboseError::from_error_kind(s, ErrorKind::TooLarge),
+                ));
+                self.abandoned = true;
+                return ("", None);
+            }
+            self.depth += 1;
+            let (rest, value) = match s.strip_prefix('[') {
+                Some(rest) => {
+                    let (rest, items) = self.array(rest);
+                    (rest, JsonValue::Array(items))
+                }
+                None => {
+                    let (rest, entries) = self.object(&s[1..]);
+                    (rest, JsonValue::Object(Map::from(entries)))
+                }
+            };
+            self.depth -= 1;
+            return (rest, Some(value));
+        }
+        match scalar(s) {
+            Ok((rest, value)) => (rest, Some(value)),
+            result => {
+                self.fail(result);
+                (sync(s), None)
+            }
+        }
+    }
+
+    fn array(&mut self, mut s: &'a str) -> (&'a str, Vec<JsonValue>) {
+        let mut items = Vec::new();
+        let mut after_comma = false;
+        loop {
+            s = skip_ws(s);
+            if let Some(rest) = s.strip_prefix(']') {
+                if after_comma {
+                    self.fail(scalar(s));
+                }
+                return (rest, items);
+            }
+            if s.is_empty() || s.starts_with('}') {
+                self.fail(char(']')(s));
+                return (s, items);
+            }
+            let (rest, item) = self.value(s);
+            items.extend(item);
+            (s, after_comma) = self.separator(rest);
+        }
+    }
+
+    fn object(&mut self, mut s: &'a str) -> (&'a str, Vec<(String, JsonValue)>) {
+        let mut entries = Vec::new();
+        let mut after_comma = false;
+        loop {
+            s = skip_ws(s);
+            if let Some(rest) = s.strip_prefix('}') {
+                if after_comma {
+                    self.fail(parse::nom_str(s));
+                }
+                return (rest, entries);
+            }
+            if s.is_empty() || s.starts_with(']') {
+                self.fail(char('}')(s));
+                return (s, entries);
+            }
+            match parse::nom_str(s) {
+                Ok((rest, key)) => {
+                    let rest = match char::<_, VerboseError<&str>>(':')(rest) {
+                        Ok((rest, _)) => rest,
+                        result => {
+                            // Carry on as if the colon were there.
+                            self.fail(result);
+                            rest
+                        }
+                    };
+                    let (rest, value) = self.value(rest);
+                    if let Some(value) = value {
+                        entries.push((key.into_owned(), value));
+                    }
+                    s = rest;
+                }
+                result => {
+                    self.fail(result);
+                    s = sync(s);
+                }
+            }
+            (s, after_comma) = self.separator(s);
+        }
+    }
+
+    // Moves past the comma after an element, if there is one. When something else follows
+    // instead, this reports the missing comma and carries on as if it were there, leaving the
+    // array or object to deal with a closing bracket or the end of the input.
+    fn separator(&mut self, s: &'a str) -> (&'a str, bool) {
+        let s = skip_ws(s);
+        if let Some(rest) = s.strip_prefix(',') {
+            return (rest, true);
+        }
+        if !(s.is_empty() || s.starts_with([']', '}'])) {
+            self.fail(char(',')(s));
+        }
+        (s, false)
+    }
+}
+
+fn scalar(s: &str) -> IResult<&str, JsonValue, VerboseError<&str>> {
+    context(
+        "value",
+        alt((
+            parse::nom_null,
+            parse::nom_bool,
+            parse::nom_string,
+            parse::nom_number,
+        )),
+    )(s)
+}
+
+fn skip_ws(s: &str) -> &str {
+    s.trim_start_matches([' ', '\t', '\n', '\r'])
+}
+
+// Skips to the next `,`, `]` or `}` that isn't inside a string. Strings can't contain a raw line
+// break, so one is taken to end an unterminated string.
+fn sync(s: &str) -> &str {
+    let mut in_string = false;
+    let mut escaped = false;
+    for (i, c) in s.char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' if in_string => escaped = true,
+            '"' => in_string = !in_string,
+            '\n' => in_string = false,
+            ',' | ']' | '}' if !in_string => return &s[i..],
+            _ => {}
+        }
+    }
+    ""
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{parse_recover, ErrorKind};
+
+    fn errors(s: &str) -> Vec<(ErrorKind, (usize, usize))> {
+        parse_recover(s)
+            .1
+            .iter()
+            .map(|e| (e.kind(), e.location().unwrap()))
+            .collect()
+    }
+
+    #[test]
+    fn recovers() {
+        assert_eq!(parse_recover("").0, None);
+        assert_eq!(errors(" "), [(ErrorKind::EmptyInput, (1, 2))]);
+
+        let s = "[1, \"unterminated\n, 3, ?]";
+        assert_eq!(parse_recover(s).0, Some(json!([1, 3])));
+        assert_eq!(
+            errors(s),
+            [
+                (ErrorKind::UnexpectedChar('\n'), (1, 18)),
+                (ErrorKind::UnexpectedChar('?'), (2, 6))
+            ]
+        );
+
+        let s = "{\"a\" 1, b: 2, \"c\": }";
+        assert_eq!(parse_recover(s).0, Some(json!({"a": 1})));
+        assert_eq!(errors(s).len(), 3);
+
+        assert_eq!(parse_recover("[1,,2,]").0, Some(json!([1, 2])));
+        // The same as `parse` gives, other than `parse` expecting a `]` in place of the first
+        // element.
+        assert_eq!(errors("[?]"), [(ErrorKind::UnexpectedChar('?'), (1, 2))]);
+        for s in ["[1,,2]", "[1, ?]", "{\"a\": x}", "[1.]"] {
+            let expected = crate::parse(s).unwrap_err();
+            let error = &parse_recover(s).1[0];
+            assert_eq!(error.kind(), expected.kind(), "{s:?}");
+            assert_eq!(error.message(), expected.message(), "{s:?}");
+        }
+        assert_eq!(errors("[1,,2,]").len(), 2);
+    }
+
+    #[test]
+    fn recovers_brackets() {
+        let s = "{\"a\": [1, 2}";
+        assert_eq!(parse_recover(s).0, Some(json!({"a": [1, 2]})));
+        assert_eq!(errors(s), [(ErrorKind::UnexpectedChar('}'), (1, 12))]);
+
+        assert_eq!(errors("[[").len(), 2);
+        assert_eq!(errors("1 2"), [(ErrorKind::TrailingData, (1, 3))]);
+
+        let deep = "[".repeat(200);
+        let (value, errors) = parse_recover(&deep);
+        assert!(value.is_some());
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].kind(), ErrorKind::TooDeep);
+    }
+}
@@ -0,0 +1,82 @@
+//! Conversions to and from [`serde_json::Value`].
+
+use crate::{JsonValue, Number};
+
+/// Converts to a [`serde_json::Value`].
+///
+/// Integers convert exactly. `serde_json` cannot represent `NaN` or infinities, so non-finite
+/// floats become [`serde_json::Value::Null`], matching how [`JsonValue`] is displayed.
+impl From<JsonValue> for serde_json::Value {
+    fn from(value: JsonValue) -> Self {
+        match value {
+            JsonValue::Null => serde_json::Value::Null,
+            JsonValue::Bool(b) => serde_json::Value::Bool(b),
+            JsonValue::String(s) => serde_json::Value::String(s),
+            JsonValue::Array(items) => {
+                serde_json::Value::Array(items.into_iter().map(Into::into).collect())
+            }
+            JsonValue::Number(n) => {
+                if let Some(n) = n.as_u64() {
+                    serde_json::Value::Number(n.into())
+                } else if let Some(n) = n.as_i64() {
+                    serde_json::Value::Number(n.into())
+                } else {
+                    serde_json::Number::from_f64(n.as_f64())
+                        .map_or(serde_json::Value::Null, serde_json::Value::Number)
+                }
+            }
+            JsonValue::Object(map) => {
+                serde_json::Value::Object(map.into_iter().map(|(k, v)| (k, v.into())).collect())
+            }
+        }
+    }
+}
+
+/// Converts from a [`serde_json::Value`].
+///
+/// Integers that fit in an `i64` or `u64` convert exactly, and anything else becomes an `f64`.
+/// With `serde_json`'s `arbitrary_precision` feature, this means digits beyond what an `f64` can
+/// hold are lost.
+impl From<serde_json::Value> for JsonValue {
+    fn from(value: serde_json::Value) -> Self {
+        match value {
+            serde_json::Value::Null => JsonValue::Null,
+            serde_json::Value::Bool(b) => JsonValue::Bool(b),
+            serde_json::Value::String(s) => JsonValue::String(s),
+            serde_json::Value::Array(items) => {
+                JsonValue::Array(items.into_iter().map(Into::into).collect())
+            }
+            serde_json::Value::Number(n) => JsonValue::Number(if let Some(n) = n.as_u64() {
+                n.into()
+            } else if let Some(n) = n.as_i64() {
+                n.into()
+            } else {
+                Number::from(n.as_f64().unwrap_or(f64::NAN))
+            }),
+            serde_json::Value::Object(map) => {
+                JsonValue::Object(map.into_iter().map(|(k, v)| (k, v.into())).collect())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::JsonValue;
+
+    #[test]
+    fn round_trip() {
+        let s = "{\"a\":[null,true,-1,18446744073709551615,0.5,\"s\"],\"b\":{}}";
+        let value = serde_json::Value::from(JsonValue::from_str(s).unwrap());
+        assert_eq!(value, serde_json::from_str::<serde_json::Value>(s).unwrap());
+        assert_eq!(JsonValue::from(value), JsonValue::from_str(s).unwrap());
+    }
+
+    #[test]
+    fn non_finite_to_null() {
+        assert_eq!(
+            serde_json::Value::from(JsonValue::Number(f64::INFINITY.into())),
+            serde_json::Value::Null
+        );
+    }
+}
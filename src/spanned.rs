@@ -0,0 +1,25 @@
+use alloc::{string::String, vec::Vec};
+
+use crate::Number;
+
+/// Something parsed from an input string, along with the byte offsets of where it was found, as
+/// returned by [`parse_spanned`](crate::parse_spanned). `&input[start..end]` is exactly the text
+/// it was parsed from, without any surrounding whitespace.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Spanned<T> {
+    pub value: T,
+    pub start: usize,
+    pub end: usize,
+}
+
+/// A parsed value like [`JsonValue`](crate::JsonValue), but with the location of every nested
+/// value and object key. Objects keep all of their entries in order, including repeated keys.
+#[derive(Clone, Debug, PartialEq)]
+pub enum SpannedValue {
+    Null,
+    Bool(bool),
+    String(String),
+    Number(Number),
+    Array(Vec<Spanned<SpannedValue>>),
+    Object(Vec<(Spanned<String>, Spanned<SpannedValue>)>),
+}
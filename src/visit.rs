@@ -0,0 +1,92 @@
+use crate::{parse::Event, Number};
+
+/// Callbacks for the parts of a JSON document, in the order they appear, as reported by
+/// [`parse_events`](crate::parse_events). Every method does nothing by default, so only the ones
+/// that are needed have to be implemented.
+///
+/// Events are reported as the input is parsed, so some may already have been seen by the time a
+/// parse error is found further on.
+///
+/// ```
+/// use jsnom::{parse_events, JsonVisitor};
+///
+/// // Collect the top-level keys of an object.
+/// #[derive(Default)]
+/// struct Keys {
+///     depth: usize,
+///     keys: Vec<String>,
+/// }
+///
+/// impl JsonVisitor for Keys {
+///     fn begin_array(&mut self) {
+///         self.depth += 1;
+///     }
+///
+///     fn end_array(&mut self) {
+///         self.depth -= 1;
+///     }
+///
+///     fn begin_object(&mut self) {
+///         self.depth += 1;
+///     }
+///
+///     fn end_object(&mut self) {
+///         self.depth -= 1;
+///     }
+///
+///     fn on_key(&mut self, key: &str) {
+///         if self.depth == 1 {
+///             self.keys.push(key.to_string());
+///         }
+///     }
+/// }
+///
+/// let mut visitor = Keys::default();
+/// parse_events("{\"a\": {\"b\": 1}, \"c\": [{\"d\": 2}]}", &mut visitor).unwrap();
+/// assert_eq!(visitor.keys, ["a", "c"]);
+/// ```
+pub trait JsonVisitor {
+    /// Called for a `null`.
+    fn on_null(&mut self) {}
+
+    /// Called for `true` or `false`.
+    fn on_bool(&mut self, _b: bool) {}
+
+    /// Called for a string value, with its escapes decoded. Object keys are reported by
+    /// [`JsonVisitor::on_key`] instead.
+    fn on_string(&mut self, _s: &str) {}
+
+    /// Called for a number.
+    fn on_number(&mut self, _n: Number) {}
+
+    /// Called at the `[` opening an array, before any of its elements.
+    fn begin_array(&mut self) {}
+
+    /// Called at the `]` closing an array, after all of its elements.
+    fn end_array(&mut self) {}
+
+    /// Called at the `{` opening an object, before any of its entries.
+    fn begin_object(&mut self) {}
+
+    /// Called with the key of each entry in an object, just before its value.
+    fn on_key(&mut self, _key: &str) {}
+
+    /// Called at the `}` closing an object, after all of its entries.
+    fn end_object(&mut self) {}
+}
+
+impl Event<'_> {
+    pub(crate) fn visit(self, visitor: &mut impl JsonVisitor) {
+        match self {
+            Event::Null => visitor.on_null(),
+            Event::Bool(b) => visitor.on_bool(b),
+            Event::String(s) => visitor.on_string(&s),
+            Event::Number(n) => visitor.on_number(n),
+            Event::BeginArray => visitor.begin_array(),
+            Event::EndArray => visitor.end_array(),
+            Event::BeginObject => visitor.begin_object(),
+            Event::Key(key) => visitor.on_key(&key),
+            Event::EndObject => visitor.end_object(),
+        }
+    }
+}
@@ -0,0 +1,248 @@
+use alloc::{
+    collections::{BTreeSet, VecDeque},
+    string::String,
+    vec,
+    vec::Vec,
+};
+use core::iter;
+
+use crate::JsonValue;
+
+/// One step on the path from a value to one of its descendants, as yielded by
+/// [`JsonValue::descendants_with_path`].
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum PathSegment {
+    /// A key in an object.
+    Key(String),
+    /// An index in an array.
+    Index(usize),
+}
+
+/// Counts describing the shape of a value, as returned by [`JsonValue::stats`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct JsonStats {
+    pub nulls: usize,
+    pub bools: usize,
+    pub strings: usize,
+    pub numbers: usize,
+    pub arrays: usize,
+    pub objects: usize,
+    /// The total length in bytes of every string and object key.
+    pub string_bytes: usize,
+    /// How deeply arrays and objects are nested, counted the same way as
+    /// [`ParseOptions::max_depth`](crate::ParseOptions::max_depth). A scalar has depth 0, and
+    /// `[[]]` has depth 2.
+    pub max_depth: usize,
+}
+
+impl JsonValue {
+    /// Count the values of each type inside this value, including itself, along with the total
+    /// size of its strings and how deeply it is nested, in a single walk.
+    ///
+    /// ```
+    /// use jsnom::json;
+    ///
+    /// let stats = json!({"tags": ["a", "bc"], "ok": true, "extra": null}).stats();
+    /// assert_eq!(stats.objects, 1);
+    /// assert_eq!(stats.arrays, 1);
+    /// assert_eq!(stats.strings, 2);
+    /// assert_eq!(stats.string_bytes, 14);
+    /// assert_eq!(stats.max_depth, 2);
+    /// ```
+    pub fn stats(&self) -> JsonStats {
+        let mut stats = JsonStats::default();
+        let mut stack = vec![(self, 0)];
+        while let Some((value, depth)) = stack.pop() {
+            match value {
+                JsonValue::Null => stats.nulls += 1,
+                JsonValue::Bool(_) => stats.bools += 1,
+                JsonValue::String(s) => {
+                    stats.strings += 1;
+                    stats.string_bytes += s.len();
+                }
+                JsonValue::Number(_) => stats.numbers += 1,
+                JsonValue::Array(items) => {
+                    stats.arrays += 1;
+                    stats.max_depth = stats.max_depth.max(depth + 1);
+                    stack.extend(items.iter().map(|item| (item, depth + 1)));
+                }
+                JsonValue::Object(map) => {
+                    stats.objects += 1;
+                    stats.max_depth = stats.max_depth.max(depth + 1);
+                    for (key, item) in map {
+                        stats.string_bytes += key.len();
+                        stack.push((item, depth + 1));
+                    }
+                }
+            }
+        }
+        stats
+    }
+
+    /// Returns an iterator over this value and everything nested inside it, depth-first with
+    /// each value before its children. Array elements and object entries are visited in order.
+    ///
+    /// The walk keeps its own stack, so it can't overflow on deeply nested values.
+    ///
+    /// ```
+    /// use jsnom::json;
+    ///
+    /// let value = json!({"name": "jsnom", "tags": ["json", {"kind": "parser"}]});
+    /// let strings: Vec<&str> = value.descendants().filter_map(|v| v.as_str()).collect();
+    /// assert_eq!(strings, ["jsnom", "json", "parser"]);
+    /// ```
+    pub fn descendants(&self) -> impl Iterator<Item = &JsonValue> {
+        let mut stack = vec![self];
+        iter::from_fn(move || {
+            let value = stack.pop()?;
+            match value {
+                JsonValue::Array(items) => stack.extend(items.iter().rev()),
+                JsonValue::Object(map) => stack.extend(map.values().rev()),
+                _ => {}
+            }
+            Some(value)
+        })
+    }
+
+    /// Like [`JsonValue::descendants`], but also yields the path from this value to each one.
+    /// The value itself comes first, with an empty path.
+    ///
+    /// ```
+    /// use jsnom::{json, PathSegment};
+    ///
+    /// let value = json!({"limits": [10, 200]});
+    /// let large: Vec<_> = value
+    ///     .descendants_with_path()
+    ///     .filter(|(_, v)| v.as_u64().is_some_and(|n| n > 100))
+    ///     .map(|(path, _)| path)
+    ///     .collect();
+    /// assert_eq!(
+    ///     large,
+    ///     [vec![PathSegment::Key("limits".to_string()), PathSegment::Index(1)]]
+    /// );
+    /// ```
+    pub fn descendants_with_path(&self) -> impl Iterator<Item = (Vec<PathSegment>, &JsonValue)> {
+        let mut stack = vec![(Vec::new(), self)];
+        iter::from_fn(move || {
+            let (path, value) = stack.pop()?;
+            let child = |segment| {
+                let mut path = path.clone();
+                path.push(segment);
+                path
+            };
+            match value {
+                JsonValue::Array(items) => stack.extend(
+                    items
+                        .iter()
+                        .enumerate()
+                        .rev()
+                        .map(|(i, item)| (child(PathSegment::Index(i)), item)),
+                ),
+                JsonValue::Object(map) => stack.extend(
+                    map.iter()
+                        .rev()
+                        .map(|(key, item)| (child(PathSegment::Key(key.clone())), item)),
+                ),
+                _ => {}
+            }
+            Some((path, value))
+        })
+    }
+
+    /// Every distinct object key used anywhere in this value, in the order they are first found by
+    /// [`JsonValue::descendants`].
+    ///
+    /// ```
+    /// use jsnom::json;
+    ///
+    /// let value = json!([{"id": 1, "tags": [{"name": "a"}]}, {"id": 2, "extra": null}]);
+    /// assert_eq!(value.all_keys(), ["id", "tags", "name", "extra"]);
+    /// ```
+    pub fn all_keys(&self) -> Vec<&str> {
+        let mut seen = BTreeSet::new();
+        self.descendants()
+            .filter_map(JsonValue::as_object)
+            .flat_map(|map| map.keys())
+            .map(String::as_str)
+            .filter(|key| seen.insert(*key))
+            .collect()
+    }
+
+    /// Returns an iterator over this value and everything nested inside it, breadth-first, so
+    /// every value at one depth comes before any value deeper down.
+    ///
+    /// ```
+    /// use jsnom::json;
+    ///
+    /// let value = json!([[1], 2, [[3]]]);
+    /// let numbers: Vec<u64> = value.breadth_first().filter_map(|v| v.as_u64()).collect();
+    /// assert_eq!(numbers, [2, 1, 3]);
+    /// ```
+    pub fn breadth_first(&self) -> impl Iterator<Item = &JsonValue> {
+        let mut queue = VecDeque::from([self]);
+        iter::from_fn(move || {
+            let value = queue.pop_front()?;
+            match value {
+                JsonValue::Array(items) => queue.extend(items),
+                JsonValue::Object(map) => queue.extend(map.values()),
+                _ => {}
+            }
+            Some(value)
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::PathSegment::{Index, Key};
+
+    #[test]
+    fn descendants_order() {
+        let value = json!({"a": [1, {"b": 2}], "c": 3});
+        let numbers: Vec<u64> = value.descendants().filter_map(|v| v.as_u64()).collect();
+        assert_eq!(numbers, [1, 2, 3]);
+
+        let paths: Vec<_> = value.descendants_with_path().map(|(p, _)| p).collect();
+        assert_eq!(
+            paths,
+            [
+                vec![],
+                vec![Key("a".to_string())],
+                vec![Key("a".to_string()), Index(0)],
+                vec![Key("a".to_string()), Index(1)],
+                vec![Key("a".to_string()), Index(1), Key("b".to_string())],
+                vec![Key("c".to_string())],
+            ]
+        );
+
+        let numbers: Vec<u64> = value.breadth_first().filter_map(|v| v.as_u64()).collect();
+        assert_eq!(numbers, [3, 1, 2]);
+    }
+
+    #[test]
+    fn stats() {
+        use super::JsonStats;
+
+        assert_eq!(
+            json!("abc").stats(),
+            JsonStats {
+                strings: 1,
+                string_bytes: 3,
+                ..JsonStats::default()
+            }
+        );
+        assert_eq!(
+            json!([[], {"k": [null, 1, false]}]).stats(),
+            JsonStats {
+                nulls: 1,
+                bools: 1,
+                numbers: 1,
+                arrays: 3,
+                objects: 1,
+                string_bytes: 1,
+                max_depth: 3,
+                ..JsonStats::default()
+            }
+        );
+    }
+}